@@ -0,0 +1,10 @@
+-- 인덱스(alias) 별 색인 작업 lease lock
+-- lock_expire_dt 가 지나면 다른 인스턴스가 lock 을 가져갈 수 있다.
+CREATE TABLE IF NOT EXISTS elastic_index_lock_tbl (
+    index_name      VARCHAR(100) NOT NULL,
+    lock_owner      VARCHAR(200) NOT NULL,
+    lock_expire_dt  DATETIME     NOT NULL,
+    reg_dt          DATETIME     NOT NULL,
+    chg_dt          DATETIME     NOT NULL,
+    PRIMARY KEY (index_name)
+);
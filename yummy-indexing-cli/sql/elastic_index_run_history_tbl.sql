@@ -0,0 +1,23 @@
+-- 색인 작업 실행 이력
+-- 실행을 시작할 때 'running' 으로 추가되고, 끝나면 결과와 통계가 기록된다.
+-- 스케쥴 재시도는 시도마다 한 행씩 남는다. (attempt)
+CREATE TABLE IF NOT EXISTS elastic_index_run_history_tbl (
+    run_id                BIGINT       NOT NULL AUTO_INCREMENT,
+    index_name            VARCHAR(100) NOT NULL,
+    function_name         VARCHAR(100) NOT NULL,
+    indexing_type         VARCHAR(20)  NOT NULL,
+    run_trigger           VARCHAR(20)  NOT NULL,
+    run_status            VARCHAR(20)  NOT NULL,
+    attempt               INT          NOT NULL DEFAULT 1,
+    start_dt              DATETIME     NOT NULL,
+    end_dt                DATETIME     NULL,
+    rows_read             BIGINT       NOT NULL DEFAULT 0,
+    documents_written     BIGINT       NOT NULL DEFAULT 0,
+    documents_deleted     BIGINT       NOT NULL DEFAULT 0,
+    documents_failed      BIGINT       NOT NULL DEFAULT 0,
+    generated_index_name  VARCHAR(200) NULL,
+    error_class           VARCHAR(20)  NULL,
+    error_message         TEXT         NULL,
+    PRIMARY KEY (run_id),
+    KEY idx_run_history_index_start (index_name, start_dt)
+);
@@ -1,11 +1,14 @@
 pub use std::{
-    collections::{HashMap, HashSet, VecDeque},
+    collections::{BTreeSet, HashMap, HashSet, VecDeque},
     env,
-    fmt::Debug,
+    error::Error as StdError,
+    fmt::{self, Debug, Display},
     fs::File,
     future::Future,
     io::{self, BufReader, Write},
     ops::Deref, //time::Duration,
+    pin::Pin,
+    process::ExitCode,
     str::FromStr,
     sync::Arc,
 };
@@ -14,16 +17,19 @@ pub use rand::{prelude::SliceRandom, rngs::StdRng, SeedableRng};
 
 pub use tokio::{
     io::AsyncReadExt,
+    net::TcpListener,
     signal,
-    sync::{Mutex, MutexGuard, OnceCell},
-    time::{Duration, Interval},
+    sync::{mpsc, oneshot, watch, Mutex, MutexGuard, OnceCell, OwnedSemaphorePermit, Semaphore},
+    time::{Duration, Instant},
 };
 
 pub use log::{error, info, warn};
 
 pub use flexi_logger::{Age, Cleanup, Criterion, FileSpec, Logger, Naming, Record};
 
-pub use chrono::{DateTime, FixedOffset, NaiveDateTime, TimeZone, Utc};
+pub use chrono::{DateTime, NaiveDateTime, TimeZone, Utc};
+
+pub use chrono_tz::Tz;
 
 pub use serde::{Deserialize, Serialize};
 
@@ -31,13 +37,31 @@ pub use serde::de::DeserializeOwned;
 
 pub use serde_json::{json, Value};
 
-pub use http::header::{HeaderMap, HeaderValue, CONTENT_TYPE};
+pub use http::{
+    header::{HeaderMap, HeaderValue, CONTENT_TYPE},
+    StatusCode,
+};
+
+pub use axum::{
+    extract::{Path, State},
+    response::IntoResponse,
+    routing::{get, post},
+    Json, Router,
+};
+
+pub use prometheus::{
+    Encoder, HistogramOpts, HistogramVec, IntCounterVec, IntGauge, IntGaugeVec, Opts, Registry,
+    TextEncoder,
+};
 
 pub use dotenv::dotenv;
 
 pub use cron::Schedule;
 
 pub use elasticsearch::{
+    auth::Credentials,
+    cat::CatIndicesParts,
+    cert::{Certificate, CertificateValidation},
     http::response::Response,
     http::transport::{ConnectionPool, Transport},
     http::transport::{SingleNodeConnectionPool, TransportBuilder},
@@ -46,15 +70,21 @@ pub use elasticsearch::{
         IndicesCreateParts, IndicesDeleteParts, IndicesGetAliasParts, IndicesGetParts,
         IndicesRefreshParts,
     },
-    BulkOperation, BulkParts, DeleteByQueryParts, DeleteParts, Elasticsearch, IndexParts,
-    SearchParts,
+    nodes::NodesInfoParts,
+    BulkParts, CountParts, DeleteByQueryParts, DeleteParts, Elasticsearch, Error as EsError,
+    IndexParts, SearchParts,
 };
 
 pub use anyhow::{anyhow, Result};
 
+pub use regex::Regex;
+
 pub use derive_new::new;
+
 pub use getset::{Getters, Setters};
 
+pub use teloxide::{prelude::Requester, types::ChatId, Bot};
+
 // pub use rdkafka:: {
 //     config::ClientConfig,
 //     consumer::Consumer,
@@ -68,8 +98,13 @@ pub use getset::{Getters, Setters};
 
 pub use sea_orm::{
     prelude::{Decimal, Expr},
-    ActiveModelBehavior, ColumnTrait, Condition, Database, DatabaseConnection, EntityTrait,
-    FromQueryResult, JoinType, QueryFilter, QueryOrder, QuerySelect, RelationTrait, Select,
+    sea_query::{Query, SimpleExpr},
+    sqlx::Error as SqlxError,
+    ActiveModelBehavior,
+    ActiveValue::{NotSet, Set},
+    ColumnTrait, Condition, ConnAcquireErr, ConnectionTrait, Database, DatabaseConnection, DbErr,
+    EntityTrait, FromQueryResult, JoinType, QueryFilter, QueryOrder, QuerySelect, RelationTrait,
+    Select, SqlErr,
 };
 
 // pub use diesel::{
@@ -82,6 +117,8 @@ pub use sea_orm::{
 
 pub use async_trait::async_trait;
 
+pub use clap::{Parser, Subcommand, ValueEnum};
+
 //use crate::repository::es_repository::*;
 //use crate::repository::kafka_repository::*;
 //pub static ELASTICSEARCH_CLIENT: OnceCell<Arc<EsRepositoryPub>> = OnceCell::new();
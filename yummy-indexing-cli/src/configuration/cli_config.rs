@@ -0,0 +1,77 @@
+use crate::common::*;
+
+#[doc = "커맨드라인 인자 - 인자가 없는 경우 'COMPILE_TYPE' 에 따라 기존 방식(스케쥴/대화형 CLI)으로 동작한다."]
+#[derive(Debug, Parser)]
+#[command(
+    name = "yummy-indexing-cli",
+    version,
+    about = "Yummy Elasticsearch indexing batch"
+)]
+pub struct CliArgs {
+    /// Skip confirmation prompts
+    #[arg(short = 'y', long, global = true)]
+    pub yes: bool,
+
+    #[command(subcommand)]
+    pub command: Option<CliCommand>,
+}
+
+#[derive(Debug, Clone, Subcommand)]
+pub enum CliCommand {
+    /// Run indexing of an index immediately
+    Run {
+        /// Index (alias) name in index_list.toml
+        index: String,
+        /// Indexing type
+        #[arg(long = "type", value_enum)]
+        indexing_type: CliIndexingType,
+    },
+    /// List the configured index schedules
+    List,
+    /// Show the current index, document count and watermark of each index
+    Status,
+    /// Run as a schedule daemon
+    Schedule,
+    /// Validate index_list.toml and the index setting files
+    ValidateConfig,
+    /// Preview the next fire times of each index schedule
+    Preview {
+        /// Index (alias) name in index_list.toml. Shows every index when omitted.
+        index: Option<String>,
+        /// Number of fire times to show per schedule
+        #[arg(long, default_value_t = 5)]
+        count: usize,
+    },
+    /// Show the recent runs of each index
+    History {
+        /// Index (alias) name in index_list.toml. Shows every index when omitted.
+        index: Option<String>,
+        /// Number of runs to show per index
+        #[arg(long, default_value_t = 10)]
+        limit: u64,
+    },
+    /// List the generations of a static index or point its alias back to one of them
+    Rollback {
+        /// Index (alias) name in index_list.toml
+        index: String,
+        /// Generation to point the alias to. Lists the generations when omitted.
+        #[arg(long)]
+        to: Option<String>,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum CliIndexingType {
+    Static,
+    Dynamic,
+}
+
+impl CliIndexingType {
+    #[doc = "index_list.toml 의 'indexing_type' 값으로 변환해주는 함수"]
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CliIndexingType::Static => "static",
+            CliIndexingType::Dynamic => "dynamic",
+        }
+    }
+}
@@ -0,0 +1,239 @@
+use crate::common::*;
+
+#[doc = "검색 등 일반 요청의 timeout(초) 기본값"]
+const DEFAULT_REQUEST_TIMEOUT_SECS: u64 = 5;
+
+#[doc = "bulk / delete_by_query 요청의 timeout(초) 기본값"]
+const DEFAULT_BULK_TIMEOUT_SECS: u64 = 120;
+
+#[doc = "인덱스 생성/삭제, alias 변경, refresh 요청의 timeout(초) 기본값"]
+const DEFAULT_INDEX_ADMIN_TIMEOUT_SECS: u64 = 60;
+
+#[doc = "Elasticsearch 접속 scheme"]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EsScheme {
+    Http,
+    Https,
+}
+
+impl EsScheme {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            EsScheme::Http => "http",
+            EsScheme::Https => "https",
+        }
+    }
+}
+
+#[doc = "Elasticsearch 인증 방식"]
+#[derive(Clone)]
+pub enum EsAuth {
+    /* 'id:api_key' 형태 */
+    ApiKey { id: String, api_key: String },
+    /* Elasticsearch 가 발급해준 base64 encoded 값 그대로 */
+    EncodedApiKey(String),
+    Basic { es_id: String, es_pw: String },
+}
+
+/* 로그에 비밀번호/API key 가 남지 않도록 가려준다. */
+impl Debug for EsAuth {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EsAuth::ApiKey { id, .. } => write!(f, "ApiKey {{ id: {:?}, api_key: \"***\" }}", id),
+            EsAuth::EncodedApiKey(_) => write!(f, "EncodedApiKey(\"***\")"),
+            EsAuth::Basic { es_id, .. } => {
+                write!(f, "Basic {{ es_id: {:?}, es_pw: \"***\" }}", es_id)
+            }
+        }
+    }
+}
+
+#[doc = "Elasticsearch 접속 설정 -> 모든 노드의 client 를 만들 때 공통으로 사용된다."]
+#[derive(Debug, Clone, Getters)]
+#[getset(get = "pub")]
+pub struct EsConnectionConfig {
+    scheme: EsScheme,
+    auth: EsAuth,
+    /* PEM 형식의 CA 인증서 묶음 */
+    ca_cert_pem: Option<Vec<u8>>,
+    /* 인증서 검증 생략 -> debug build 에서만 허용된다. */
+    skip_tls_verify: bool,
+    request_timeout: Duration,
+    bulk_timeout: Duration,
+    index_admin_timeout: Duration,
+}
+
+impl EsConnectionConfig {
+    #[doc = "환경변수로부터 Elasticsearch 접속 설정을 만들어주는 함수"]
+    /// * `ES_SCHEME` - http(기본값) 또는 https
+    /// * `ES_API_KEY` - 'id:api_key' 또는 base64 encoded API key -> 있으면 `ES_ID`/`ES_PW` 대신 사용한다.
+    /// * `ES_CA_CERT_PATH` - PEM 형식의 CA 인증서 파일 경로
+    /// * `ES_TLS_SKIP_VERIFY` - true 이면 인증서 검증을 생략한다. (개발용, release build 에서는 오류)
+    /// * `ES_REQUEST_TIMEOUT_SECS`, `ES_BULK_TIMEOUT_SECS`, `ES_INDEX_ADMIN_TIMEOUT_SECS` - 요청 종류별 timeout
+    ///
+    /// # Returns
+    /// * Result<EsConnectionConfig, anyhow::Error>
+    pub fn from_env() -> Result<Self, anyhow::Error> {
+        let scheme: EsScheme = match get_env_value("ES_SCHEME") {
+            None => EsScheme::Http,
+            Some(scheme) if scheme.eq_ignore_ascii_case("http") => EsScheme::Http,
+            Some(scheme) if scheme.eq_ignore_ascii_case("https") => EsScheme::Https,
+            Some(scheme) => {
+                return Err(anyhow!(
+                    "[Error][EsConnectionConfig::from_env()] 'ES_SCHEME' must be 'http' or 'https'. (value: {})",
+                    scheme
+                ))
+            }
+        };
+
+        let auth: EsAuth = match get_env_value("ES_API_KEY") {
+            Some(api_key) => match api_key.split_once(':') {
+                Some((id, api_key)) => EsAuth::ApiKey {
+                    id: id.to_string(),
+                    api_key: api_key.to_string(),
+                },
+                None => EsAuth::EncodedApiKey(api_key),
+            },
+            None => {
+                let es_id: String = get_env_value("ES_ID").ok_or_else(|| {
+                    anyhow!("[ENV file read Error][EsConnectionConfig::from_env()] 'ES_ID' must be set when 'ES_API_KEY' is not set")
+                })?;
+                let es_pw: String = get_env_value("ES_PW").ok_or_else(|| {
+                    anyhow!("[ENV file read Error][EsConnectionConfig::from_env()] 'ES_PW' must be set when 'ES_API_KEY' is not set")
+                })?;
+
+                EsAuth::Basic { es_id, es_pw }
+            }
+        };
+
+        let ca_cert_pem: Option<Vec<u8>> = match get_env_value("ES_CA_CERT_PATH") {
+            Some(ca_cert_path) => {
+                let ca_cert_pem: Vec<u8> = std::fs::read(&ca_cert_path).map_err(|e| {
+                    anyhow!(
+                        "[Error][EsConnectionConfig::from_env()] Failed to read the CA certificate '{}' : {:?}",
+                        ca_cert_path,
+                        e
+                    )
+                })?;
+
+                /* 인증서 형식 오류는 노드에 접속하기 전에 알려준다. */
+                Certificate::from_pem(&ca_cert_pem).map_err(|e| {
+                    anyhow!(
+                        "[Error][EsConnectionConfig::from_env()] Invalid CA certificate '{}' : {:?}",
+                        ca_cert_path,
+                        e
+                    )
+                })?;
+
+                Some(ca_cert_pem)
+            }
+            None => None,
+        };
+
+        let skip_tls_verify: bool = get_env_value("ES_TLS_SKIP_VERIFY")
+            .map(|value| value.eq_ignore_ascii_case("true"))
+            .unwrap_or(false);
+
+        if skip_tls_verify {
+            if !cfg!(debug_assertions) {
+                return Err(anyhow!(
+                    "[Error][EsConnectionConfig::from_env()] 'ES_TLS_SKIP_VERIFY' is only allowed in development (debug) builds."
+                ));
+            }
+
+            warn!("'ES_TLS_SKIP_VERIFY' is set. Elasticsearch certificates will NOT be verified. Never use this in production.");
+        }
+
+        if ca_cert_pem.is_some() && skip_tls_verify {
+            return Err(anyhow!(
+                "[Error][EsConnectionConfig::from_env()] 'ES_CA_CERT_PATH' and 'ES_TLS_SKIP_VERIFY' cannot be used together."
+            ));
+        }
+
+        Ok(EsConnectionConfig {
+            scheme,
+            auth,
+            ca_cert_pem,
+            skip_tls_verify,
+            request_timeout: get_env_timeout(
+                "ES_REQUEST_TIMEOUT_SECS",
+                DEFAULT_REQUEST_TIMEOUT_SECS,
+            )?,
+            bulk_timeout: get_env_timeout("ES_BULK_TIMEOUT_SECS", DEFAULT_BULK_TIMEOUT_SECS)?,
+            index_admin_timeout: get_env_timeout(
+                "ES_INDEX_ADMIN_TIMEOUT_SECS",
+                DEFAULT_INDEX_ADMIN_TIMEOUT_SECS,
+            )?,
+        })
+    }
+
+    #[doc = "노드 주소에 scheme 을 붙여 접속 URL 을 만들어주는 함수 -> 주소에 scheme 이 이미 있으면 그대로 사용한다."]
+    /// # Arguments
+    /// * `host` - 노드 주소 (host:port)
+    ///
+    /// # Returns
+    /// * Result<Url, anyhow::Error>
+    pub fn get_node_url(&self, host: &str) -> Result<Url, anyhow::Error> {
+        let node_url: String = if host.contains("://") {
+            host.to_string()
+        } else {
+            format!("{}://{}", self.scheme.as_str(), host)
+        };
+
+        Ok(Url::parse(&node_url)?)
+    }
+
+    #[doc = "인증 정보를 Elasticsearch client 용으로 바꿔주는 함수"]
+    pub fn get_credentials(&self) -> Credentials {
+        match &self.auth {
+            EsAuth::ApiKey { id, api_key } => Credentials::ApiKey(id.clone(), api_key.clone()),
+            EsAuth::EncodedApiKey(api_key) => Credentials::EncodedApiKey(api_key.clone()),
+            EsAuth::Basic { es_id, es_pw } => Credentials::Basic(es_id.clone(), es_pw.clone()),
+        }
+    }
+
+    #[doc = "인증서 검증 방식을 만들어주는 함수"]
+    /// # Returns
+    /// * Result<CertificateValidation, anyhow::Error>
+    pub fn get_cert_validation(&self) -> Result<CertificateValidation, anyhow::Error> {
+        if self.skip_tls_verify {
+            return Ok(CertificateValidation::None);
+        }
+
+        match &self.ca_cert_pem {
+            Some(ca_cert_pem) => Ok(CertificateValidation::Full(Certificate::from_pem(
+                ca_cert_pem,
+            )?)),
+            None => Ok(CertificateValidation::Default),
+        }
+    }
+}
+
+#[doc = "비어있지 않은 환경변수 값을 가져와주는 함수"]
+fn get_env_value(env_name: &str) -> Option<String> {
+    env::var(env_name)
+        .ok()
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
+
+#[doc = "초 단위 timeout 환경변수를 읽어주는 함수 -> 없으면 기본값을 사용한다."]
+/// # Arguments
+/// * `env_name` - 환경변수 이름
+/// * `default_secs` - 기본값
+///
+/// # Returns
+/// * Result<Duration, anyhow::Error>
+fn get_env_timeout(env_name: &str, default_secs: u64) -> Result<Duration, anyhow::Error> {
+    match get_env_value(env_name) {
+        Some(secs) => match secs.parse::<u64>() {
+            Ok(secs) if secs > 0 => Ok(Duration::from_secs(secs)),
+            _ => Err(anyhow!(
+                "[Error][get_env_timeout()] '{}' must be a positive number of seconds. (value: {})",
+                env_name,
+                secs
+            )),
+        },
+        None => Ok(Duration::from_secs(default_secs)),
+    }
+}
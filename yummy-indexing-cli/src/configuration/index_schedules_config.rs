@@ -1,5 +1,25 @@
 use crate::common::*;
 
+use crate::utils_module::io_utils::*;
+
+use crate::models::run_error::*;
+
+#[doc = "MainController::main_task() 에서 실행할 수 있는 색인 함수 이름들"]
+pub const INDEXING_FUNCTION_NAMES: [&str; 3] = [
+    "store_static_index",
+    "store_dynamic_index",
+    "auto_complete_static_index",
+];
+
+#[doc = "timezone 설정이 없는 경우 cron 표현식을 해석할 기본 timezone"]
+pub const DEFAULT_TIMEZONE: &str = "Asia/Seoul";
+
+#[doc = "스케쥴 재시도 대기시간의 상한(ms) -> backoff 로 늘어나더라도 하루를 넘기지 않는다."]
+pub const MAX_RETRY_DELAY_MS: u64 = 24 * 60 * 60 * 1000;
+
+#[doc = "인덱스 lock 의 최소 lease 시간(초) -> heartbeat 는 lease 의 1/3 주기로 갱신된다."]
+pub const MIN_LOCK_LEASE_SECS: u64 = 3;
+
 #[derive(Debug, Deserialize, Serialize, Getters, Clone)]
 #[getset(get = "pub")]
 pub struct IndexSchedules {
@@ -10,10 +30,365 @@ pub struct IndexSchedules {
     pub function_name: String,
     pub sql_batch_size: usize,
     pub es_batch_size: usize,
+    pub es_max_bulk_bytes: Option<usize>,
+    #[serde(default = "default_bulk_retry_count")]
+    pub bulk_retry_count: usize,
+    #[serde(default = "default_bulk_retry_delay_ms")]
+    pub bulk_retry_delay_ms: u64,
+    #[serde(default = "default_bulk_failure_threshold")]
+    pub bulk_failure_threshold: f64,
+    pub max_drop_percentage: Option<f64>,
+    pub min_doc_count: Option<u64>,
+    #[serde(default = "default_retention_count")]
+    pub retention_count: usize,
+    #[serde(default)]
+    pub overlap_policy: OverlapPolicy,
+    #[serde(default = "default_lock_lease_secs")]
+    pub lock_lease_secs: u64,
+    pub timezone: Option<String>,
+    #[serde(default)]
+    pub catch_up: CatchUpPolicy,
+    #[serde(default = "default_retry_max_attempts")]
+    pub retry_max_attempts: u32,
+    #[serde(default = "default_retry_initial_delay_ms")]
+    pub retry_initial_delay_ms: u64,
+    #[serde(default = "default_retry_backoff_factor")]
+    pub retry_backoff_factor: f64,
+    #[serde(default = "default_retry_on")]
+    pub retry_on: Vec<RunErrorClass>,
+    pub watermark_lag_alert_secs: Option<u64>,
+}
+
+#[doc = "이전 실행이 끝나기 전에 같은 인덱스의 다음 실행 시각이 된 경우의 처리 방식"]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OverlapPolicy {
+    /* 이전 실행이 끝날때까지 이번 실행을 건너뛴다. */
+    #[default]
+    Skip,
+    /* 이전 실행이 끝나면 이어서 실행한다. */
+    Queue,
+    /* 이전 실행을 취소하고 새로 실행한다. */
+    Cancel,
+}
+
+#[doc = "데몬이 내려가 있던 동안 놓친 실행 시각의 처리 방식 -> 데몬 시작 시점에 한번만 확인한다."]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CatchUpPolicy {
+    /* 놓친 실행은 버리고 다음 실행 시각을 기다린다. */
+    #[default]
+    None,
+    /* 놓친 실행이 있으면 몇번을 놓쳤든 한번만 실행한다. */
+    Once,
 }
 
 #[derive(Debug, Deserialize, Serialize, Getters, Clone)]
 #[getset(get = "pub")]
 pub struct IndexSchedulesConfig {
+    pub timezone: Option<String>,
+    #[serde(default)]
+    pub notification: NotificationConfig,
     pub index: Vec<IndexSchedules>,
 }
+
+#[doc = "Telegram 알림 설정 -> bot token 은 설정 파일에 남지 않도록 환경변수 이름으로 지정한다."]
+#[derive(Debug, Deserialize, Serialize, Getters, Clone, Default)]
+#[getset(get = "pub")]
+pub struct NotificationConfig {
+    /* bot token 을 읽을 환경변수 이름 -> 없으면 'TELEGRAM_BOT_TOKEN' */
+    pub bot_token_env: Option<String>,
+    /* 알림을 받을 chat id 목록 -> 비어있으면 'TELEGRAM_CHAT_IDS' 환경변수(콤마 구분)를 사용한다. */
+    #[serde(default)]
+    pub chat_ids: Vec<i64>,
+    /* 인덱스별 문서 수 요약을 보낼 cron 표현식 -> 없으면 요약을 보내지 않는다. */
+    pub daily_summary_time: Option<String>,
+}
+
+impl IndexSchedulesConfig {
+    #[doc = "일일 요약 cron 표현식을 파싱해주는 함수"]
+    /// # Returns
+    /// * Result<Option<Schedule>, anyhow::Error> - 일일 요약 설정이 없으면 None
+    pub fn daily_summary_schedule(&self) -> Result<Option<Schedule>, anyhow::Error> {
+        self.notification
+            .daily_summary_time
+            .as_deref()
+            .map(|summary_time| {
+                Schedule::from_str(summary_time)
+                    .map_err(|e| anyhow!("invalid daily_summary_time '{}': {}", summary_time, e))
+            })
+            .transpose()
+    }
+
+    #[doc = "전역 timezone 을 가져와주는 함수 -> 인덱스에 속하지 않는 스케쥴(일일 요약)에 사용된다."]
+    /// # Returns
+    /// * Result<Tz, anyhow::Error>
+    pub fn global_timezone(&self) -> Result<Tz, anyhow::Error> {
+        let timezone: &str = self.timezone.as_deref().unwrap_or(DEFAULT_TIMEZONE);
+
+        timezone
+            .parse::<Tz>()
+            .map_err(|e| anyhow!("invalid timezone '{}': {}", timezone, e))
+    }
+
+    #[doc = "인덱스에 속하지 않는 전역 설정(timezone, 일일 요약 cron 표현식)을 검증해주는 함수"]
+    /// # Returns
+    /// * Vec<String> - 발견된 문제 목록. 비어있으면 정상
+    pub fn validate_global(&self) -> Vec<String> {
+        let mut problems: Vec<String> = Vec::new();
+
+        if let Err(e) = self.global_timezone() {
+            problems.push(format!("global: {}", e));
+        }
+
+        if let Err(e) = self.daily_summary_schedule() {
+            problems.push(format!("notification: {}", e));
+        }
+
+        problems
+    }
+
+    #[doc = "스케쥴러를 띄우는데 필요한 cron 표현식과 timezone 을 모두 검증해주는 함수"]
+    /// # Returns
+    /// * Vec<String> - 발견된 문제 목록. 비어있으면 정상
+    pub fn validate_schedules(&self) -> Vec<String> {
+        let mut problems: Vec<String> = self
+            .index
+            .iter()
+            .flat_map(|schedule| {
+                schedule
+                    .validate_schedule()
+                    .into_iter()
+                    .map(move |problem| {
+                        format!(
+                            "{} ({}): {}",
+                            schedule.index_name(),
+                            schedule.function_name(),
+                            problem
+                        )
+                    })
+            })
+            .collect();
+
+        problems.extend(self.validate_global());
+        problems
+    }
+
+    #[doc = "인덱스별 설정이 없는 항목에 전역 설정값을 채워주는 함수"]
+    pub fn inherit_global_settings(&mut self) {
+        for index in &mut self.index {
+            if index.timezone.is_none() {
+                index.timezone = self.timezone.clone();
+            }
+        }
+    }
+}
+
+impl IndexSchedules {
+    #[doc = "인덱스 스케쥴 설정값을 검증해주는 함수"]
+    /// # Returns
+    /// * Vec<String> - 잘못된 설정에 대한 설명 목록 (비어있으면 정상)
+    pub fn validate(&self) -> Vec<String> {
+        let mut problems: Vec<String> = self.validate_schedule();
+
+        if !INDEXING_FUNCTION_NAMES.contains(&self.function_name.as_str()) {
+            problems.push(format!("unknown function_name '{}'", self.function_name));
+        }
+
+        match self.indexing_type.as_str() {
+            "static" => match &self.setting_path {
+                Some(setting_path) => {
+                    if let Err(e) = read_json_from_file(setting_path) {
+                        problems.push(format!("invalid setting_path '{}': {}", setting_path, e));
+                    }
+                }
+                None => problems.push("static index requires 'setting_path'".to_string()),
+            },
+            "dynamic" => (),
+            other => problems.push(format!(
+                "indexing_type must be 'static' or 'dynamic': '{}'",
+                other
+            )),
+        }
+
+        if self.sql_batch_size == 0 || self.es_batch_size == 0 {
+            problems.push("sql_batch_size and es_batch_size must be greater than 0".to_string());
+        }
+
+        if self.es_max_bulk_bytes == Some(0) {
+            problems.push("es_max_bulk_bytes must be greater than 0".to_string());
+        }
+
+        if !(0.0..=1.0).contains(&self.bulk_failure_threshold) {
+            problems.push(format!(
+                "bulk_failure_threshold must be between 0 and 1: {}",
+                self.bulk_failure_threshold
+            ));
+        }
+
+        if self.retention_count == 0 {
+            problems.push("retention_count must be greater than 0".to_string());
+        }
+
+        if self.lock_lease_secs < MIN_LOCK_LEASE_SECS {
+            problems.push(format!(
+                "lock_lease_secs must be at least {}: {}",
+                MIN_LOCK_LEASE_SECS, self.lock_lease_secs
+            ));
+        }
+
+        if self.watermark_lag_alert_secs.is_some() && self.indexing_type != "dynamic" {
+            problems.push("watermark_lag_alert_secs is only for dynamic indexes".to_string());
+        }
+
+        if self.retry_max_attempts == 0 {
+            problems.push("retry_max_attempts must be greater than 0".to_string());
+        }
+
+        if !self.retry_backoff_factor.is_finite() || self.retry_backoff_factor < 1.0 {
+            problems.push(format!(
+                "retry_backoff_factor must be at least 1: {}",
+                self.retry_backoff_factor
+            ));
+        }
+
+        problems
+    }
+
+    #[doc = "cron 표현식과 timezone 만 검증해주는 함수 -> 스케쥴러 시작 전에 호출된다."]
+    /// # Returns
+    /// * Vec<String> - 잘못된 설정에 대한 설명 목록 (비어있으면 정상)
+    pub fn validate_schedule(&self) -> Vec<String> {
+        [self.cron_schedule().err(), self.cron_timezone().err()]
+            .into_iter()
+            .flatten()
+            .map(|e| e.to_string())
+            .collect()
+    }
+
+    #[doc = "cron 표현식을 파싱해주는 함수"]
+    /// # Returns
+    /// * Result<Schedule, anyhow::Error>
+    pub fn cron_schedule(&self) -> Result<Schedule, anyhow::Error> {
+        Schedule::from_str(&self.time)
+            .map_err(|e| anyhow!("invalid cron expression '{}': {}", self.time, e))
+    }
+
+    #[doc = "cron 표현식을 해석할 timezone 을 가져와주는 함수 -> IANA 이름 (예: Asia/Seoul)"]
+    /// # Returns
+    /// * Result<Tz, anyhow::Error>
+    pub fn cron_timezone(&self) -> Result<Tz, anyhow::Error> {
+        let timezone: &str = self.timezone.as_deref().unwrap_or(DEFAULT_TIMEZONE);
+
+        timezone
+            .parse::<Tz>()
+            .map_err(|e| anyhow!("invalid timezone '{}': {}", timezone, e))
+    }
+
+    #[doc = "실패한 스케쥴 실행을 다시 시도할지 판단해주는 함수"]
+    /// # Arguments
+    /// * `attempt` - 실패한 실행의 시도 횟수 (1부터 시작)
+    /// * `error_class` - 실패 원인 분류
+    ///
+    /// # Returns
+    /// * bool
+    pub fn should_retry(&self, attempt: u32, error_class: RunErrorClass) -> bool {
+        attempt < self.retry_max_attempts && self.retry_on.contains(&error_class)
+    }
+
+    #[doc = "다음 재시도까지의 대기시간을 구해주는 함수 -> initial_delay * backoff_factor^(attempt - 1)"]
+    /// # Arguments
+    /// * `attempt` - 실패한 실행의 시도 횟수 (1부터 시작)
+    ///
+    /// # Returns
+    /// * Duration
+    pub fn retry_delay(&self, attempt: u32) -> Duration {
+        let exponent: i32 = attempt.saturating_sub(1).min(i32::MAX as u32) as i32;
+        let delay_ms: f64 =
+            self.retry_initial_delay_ms as f64 * self.retry_backoff_factor.powi(exponent);
+
+        /* 너무 큰 값은 Duration 범위를 넘지 않도록 하루로 제한한다. */
+        Duration::from_millis(delay_ms.min(MAX_RETRY_DELAY_MS as f64) as u64)
+    }
+
+    #[doc = "최종 색인 실패 문서 비율이 허용치(bulk_failure_threshold)를 넘는지 확인해주는 함수"]
+    /// # Arguments
+    /// * `index_name` - 색인 대상 인덱스 이름
+    /// * `failed_cnt` - 재시도 후에도 색인에 실패한 문서 수
+    /// * `total_cnt` - 색인을 시도한 전체 문서 수
+    ///
+    /// # Returns
+    /// * Result<(), anyhow::Error>
+    pub fn check_bulk_failure_ratio(
+        &self,
+        index_name: &str,
+        failed_cnt: usize,
+        total_cnt: usize,
+    ) -> Result<(), anyhow::Error> {
+        if failed_cnt == 0 || total_cnt == 0 {
+            return Ok(());
+        }
+
+        let failure_ratio: f64 = failed_cnt as f64 / total_cnt as f64;
+
+        if failure_ratio > self.bulk_failure_threshold {
+            return Err(anyhow!(
+                "[Error][check_bulk_failure_ratio()] The failure ratio of '{}' exceeds the threshold.: {:.4} > {}",
+                index_name,
+                failure_ratio,
+                self.bulk_failure_threshold
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+#[doc = "bulk 색인 실패 문서(429, 5xx)의 최대 재시도 횟수 기본값"]
+fn default_bulk_retry_count() -> usize {
+    3
+}
+
+#[doc = "bulk 색인 재시도 최초 대기시간(ms) 기본값 -> 재시도마다 두배씩 늘어난다."]
+fn default_bulk_retry_delay_ms() -> u64 {
+    1000
+}
+
+#[doc = "전체 문서 대비 최종 색인 실패 문서 비율의 허용치 기본값 -> 넘어서면 색인 작업을 실패로 처리한다."]
+fn default_bulk_failure_threshold() -> f64 {
+    0.01
+}
+
+#[doc = "alias 별로 보관할 정적색인 인덱스 세대 수 기본값 -> 현재 alias 에 맵핑된 인덱스만 남긴다."]
+fn default_retention_count() -> usize {
+    1
+}
+
+#[doc = "인덱스 lock 의 lease 시간(초) 기본값 -> heartbeat 가 끊긴 뒤 이 시간이 지나면 다른 인스턴스가 lock 을 가져갈 수 있다."]
+fn default_lock_lease_secs() -> u64 {
+    60
+}
+
+#[doc = "스케쥴 실행의 최대 시도 횟수 기본값 -> 1이면 재시도하지 않는다."]
+fn default_retry_max_attempts() -> u32 {
+    1
+}
+
+#[doc = "스케쥴 실행 재시도 최초 대기시간(ms) 기본값"]
+fn default_retry_initial_delay_ms() -> u64 {
+    30000
+}
+
+#[doc = "스케쥴 실행 재시도마다 대기시간에 곱해지는 값의 기본값"]
+fn default_retry_backoff_factor() -> f64 {
+    2.0
+}
+
+#[doc = "스케쥴 실행을 재시도할 실패 원인 기본값 -> 일시적인 실패만 재시도한다."]
+fn default_retry_on() -> Vec<RunErrorClass> {
+    vec![
+        RunErrorClass::Connection,
+        RunErrorClass::Timeout,
+        RunErrorClass::RateLimited,
+    ]
+}
@@ -1,2 +1,4 @@
+pub mod cli_config;
+pub mod es_connection_config;
 pub mod index_schedules_config;
 pub mod system_config;
@@ -0,0 +1,209 @@
+use crate::common::*;
+
+use crate::controller::main_controller::*;
+
+use crate::models::scheduler_state::*;
+
+use crate::services::es_query_service::*;
+use crate::services::notification_service::*;
+use crate::services::query_service::*;
+
+#[doc = "관리 API 를 열어주는 함수 -> 스케쥴 모드에서만 실행되며, 외부에 노출되지 않도록 loopback 주소에 바인딩해야 한다."]
+/// # Arguments
+/// * `listen_addr` - 바인딩할 주소 (예: 127.0.0.1:9899)
+/// * `controller_arc` - 스케쥴러가 사용중인 메인 컨트롤러
+///
+/// # Returns
+/// * Result<(), anyhow::Error>
+pub async fn serve_admin_api<Q, E, N>(
+    listen_addr: String,
+    controller_arc: Arc<MainController<Q, E, N>>,
+) -> Result<(), anyhow::Error>
+where
+    Q: QueryService + 'static,
+    E: EsQueryService + 'static,
+    N: NotificationService + 'static,
+{
+    let router: Router = Router::new()
+        .route("/schedules", get(get_schedules::<Q, E, N>))
+        .route("/schedules/:index_name/cancel", post(cancel_run::<Q, E, N>))
+        .route("/schedules/:index_name/pause", post(pause_index::<Q, E, N>))
+        .route(
+            "/schedules/:index_name/resume",
+            post(resume_index::<Q, E, N>),
+        )
+        .route(
+            "/schedules/:index_name/:function_name/run",
+            post(trigger_run::<Q, E, N>),
+        )
+        .route(
+            "/schedules/:index_name/:function_name/pause",
+            post(pause_schedule::<Q, E, N>),
+        )
+        .route(
+            "/schedules/:index_name/:function_name/resume",
+            post(resume_schedule::<Q, E, N>),
+        )
+        .with_state(controller_arc);
+
+    let listener: TcpListener = TcpListener::bind(&listen_addr).await.map_err(|e| {
+        anyhow!(
+            "[Error][serve_admin_api()] Failed to bind '{}': {}",
+            listen_addr,
+            e
+        )
+    })?;
+
+    info!(
+        "[serve_admin_api()] Admin API is served on http://{}",
+        listen_addr
+    );
+
+    axum::serve(listener, router).await?;
+
+    Ok(())
+}
+
+#[doc = "GET /schedules - 스케쥴 목록과 다음 실행 시각, 마지막 결과를 응답해주는 함수"]
+async fn get_schedules<Q, E, N>(
+    State(controller_arc): State<Arc<MainController<Q, E, N>>>,
+) -> impl IntoResponse
+where
+    Q: QueryService + 'static,
+    E: EsQueryService + 'static,
+    N: NotificationService + 'static,
+{
+    let schedule_statuses: Vec<ScheduleStatus> = controller_arc.get_schedule_statuses().await;
+
+    Json(schedule_statuses)
+}
+
+#[doc = "POST /schedules/{index}/{function}/run - 색인 작업을 바로 실행해주는 함수"]
+async fn trigger_run<Q, E, N>(
+    State(controller_arc): State<Arc<MainController<Q, E, N>>>,
+    Path((index_name, function_name)): Path<(String, String)>,
+) -> impl IntoResponse
+where
+    Q: QueryService + 'static,
+    E: EsQueryService + 'static,
+    N: NotificationService + 'static,
+{
+    run_scheduler_command(
+        &controller_arc,
+        &index_name,
+        SchedulerCommand::Trigger(function_name),
+    )
+    .await
+}
+
+#[doc = "POST /schedules/{index}/cancel - 실행중인 색인 작업에 취소 신호를 보내주는 함수 -> 작업은 lock 해제와 실행 이력 기록을 마친 뒤 끝난다."]
+async fn cancel_run<Q, E, N>(
+    State(controller_arc): State<Arc<MainController<Q, E, N>>>,
+    Path(index_name): Path<String>,
+) -> impl IntoResponse
+where
+    Q: QueryService + 'static,
+    E: EsQueryService + 'static,
+    N: NotificationService + 'static,
+{
+    run_scheduler_command(&controller_arc, &index_name, SchedulerCommand::Cancel).await
+}
+
+#[doc = "POST /schedules/{index}/pause - 인덱스의 모든 스케쥴을 멈춰주는 함수"]
+async fn pause_index<Q, E, N>(
+    State(controller_arc): State<Arc<MainController<Q, E, N>>>,
+    Path(index_name): Path<String>,
+) -> impl IntoResponse
+where
+    Q: QueryService + 'static,
+    E: EsQueryService + 'static,
+    N: NotificationService + 'static,
+{
+    run_scheduler_command(&controller_arc, &index_name, SchedulerCommand::Pause(None)).await
+}
+
+#[doc = "POST /schedules/{index}/resume - 인덱스의 모든 스케쥴을 다시 시작해주는 함수"]
+async fn resume_index<Q, E, N>(
+    State(controller_arc): State<Arc<MainController<Q, E, N>>>,
+    Path(index_name): Path<String>,
+) -> impl IntoResponse
+where
+    Q: QueryService + 'static,
+    E: EsQueryService + 'static,
+    N: NotificationService + 'static,
+{
+    run_scheduler_command(&controller_arc, &index_name, SchedulerCommand::Resume(None)).await
+}
+
+#[doc = "POST /schedules/{index}/{function}/pause - 스케쥴 하나를 멈춰주는 함수"]
+async fn pause_schedule<Q, E, N>(
+    State(controller_arc): State<Arc<MainController<Q, E, N>>>,
+    Path((index_name, function_name)): Path<(String, String)>,
+) -> impl IntoResponse
+where
+    Q: QueryService + 'static,
+    E: EsQueryService + 'static,
+    N: NotificationService + 'static,
+{
+    run_scheduler_command(
+        &controller_arc,
+        &index_name,
+        SchedulerCommand::Pause(Some(function_name)),
+    )
+    .await
+}
+
+#[doc = "POST /schedules/{index}/{function}/resume - 멈춘 스케쥴 하나를 다시 시작해주는 함수"]
+async fn resume_schedule<Q, E, N>(
+    State(controller_arc): State<Arc<MainController<Q, E, N>>>,
+    Path((index_name, function_name)): Path<(String, String)>,
+) -> impl IntoResponse
+where
+    Q: QueryService + 'static,
+    E: EsQueryService + 'static,
+    N: NotificationService + 'static,
+{
+    run_scheduler_command(
+        &controller_arc,
+        &index_name,
+        SchedulerCommand::Resume(Some(function_name)),
+    )
+    .await
+}
+
+#[doc = "스케쥴러 명령을 보내고 결과를 http 응답으로 바꿔주는 함수"]
+/// # Arguments
+/// * `controller_arc` - 메인 컨트롤러
+/// * `index_name` - 인덱스(alias) 이름
+/// * `command` - 스케쥴러 명령
+///
+/// # Returns
+/// * (StatusCode, Json<Value>)
+async fn run_scheduler_command<Q, E, N>(
+    controller_arc: &MainController<Q, E, N>,
+    index_name: &str,
+    command: SchedulerCommand,
+) -> (StatusCode, Json<Value>)
+where
+    Q: QueryService,
+    E: EsQueryService,
+    N: NotificationService,
+{
+    match controller_arc
+        .send_scheduler_command(index_name, command)
+        .await
+    {
+        Ok(Some(result)) => (StatusCode::OK, Json(json!({ "result": result }))),
+        Ok(None) => (
+            StatusCode::NOT_FOUND,
+            Json(json!({ "error": format!("There is no scheduler for '{}'.", index_name) })),
+        ),
+        Err(e) => {
+            warn!("[run_scheduler_command()] {:#}", e);
+            (
+                StatusCode::BAD_REQUEST,
+                Json(json!({ "error": format!("{:#}", e) })),
+            )
+        }
+    }
+}
@@ -1,268 +1,1563 @@
 use crate::common::*;
 
 use crate::services::es_query_service::*;
+use crate::services::notification_service::*;
 use crate::services::query_service::*;
 
-use crate::configuration::{index_schedules_config::*, system_config::*};
+use crate::configuration::{cli_config::*, index_schedules_config::*};
 
+use crate::models::auto_complete::*;
+use crate::models::index_generation::*;
+use crate::models::indexing_error::*;
+use crate::models::indexing_stats::*;
+use crate::models::run_error::*;
+use crate::models::run_report::*;
+use crate::models::scheduler_state::*;
 use crate::models::store_to_elastic::*;
 use crate::models::store_types::*;
 
+use crate::entity::elastic_index_run_history_tbl;
+
+use crate::utils_module::io_utils::*;
+use crate::utils_module::metrics_utils::*;
 use crate::utils_module::time_utils::*;
 
+#[doc = "MySQL 읽기 단계와 Elasticsearch 색인 단계 사이에 쌓아둘 수 있는 최대 페이지 수"]
+const STORE_PAGE_CHANNEL_SIZE: usize = 2;
+
+#[doc = "관리 API 에서 스케쥴러로 보낼 수 있는 최대 대기 명령 수"]
+const SCHEDULER_COMMAND_CHANNEL_SIZE: usize = 16;
+
+#[doc = "스케쥴러가 실행중인 색인 작업 -> 취소할 때도 future 를 버리지 않고 작업이 끝날때까지 기다린다."]
+type IndexingRun<'a> = Pin<Box<dyn Future<Output = Result<RunReport, anyhow::Error>> + Send + 'a>>;
+
+#[doc = "색인 작업의 취소 신호 -> Some(취소 사유) 가 되면 작업이 정리를 마치고 끝난다."]
+type CancelSignal = watch::Receiver<Option<String>>;
+
+#[doc = "(실행중인 스케쥴 정보, 실행시킨 주체, 취소 신호를 보내는 쪽, 색인 작업)"]
+type RunningTask<'a> = (
+    IndexSchedules,
+    RunTrigger,
+    watch::Sender<Option<String>>,
+    IndexingRun<'a>,
+);
+
 #[derive(Debug, new)]
-pub struct MainController<Q: QueryService, E: EsQueryService> {
+pub struct MainController<Q: QueryService, E: EsQueryService, N: NotificationService> {
     query_service: Q,
     es_query_service: E,
+    notification_service: N,
+    /* 인덱스 lock 의 소유자로 기록될 이 프로세스의 식별자 */
+    #[new(value = "create_lock_owner_id()")]
+    lock_owner: String,
+    /* 인덱스(alias)별 스케쥴러 명령 채널 */
+    #[new(default)]
+    schedulers: Mutex<HashMap<String, mpsc::Sender<SchedulerRequest>>>,
+    /* 인덱스(alias)별 스케쥴 상태 -> 스케쥴러 루프가 갱신한다. */
+    #[new(default)]
+    schedule_statuses: Mutex<HashMap<String, Vec<ScheduleStatus>>>,
 }
 
-impl<Q: QueryService, E: EsQueryService> MainController<Q, E> {
-    #[doc = "메인 스케쥴러 함수"]
+impl<Q: QueryService, E: EsQueryService, N: NotificationService> MainController<Q, E, N> {
+    #[doc = "메인 스케쥴러 함수 -> 같은 인덱스의 스케쥴들을 하나의 루프에서 관리하여 동시에 실행되지 않도록 해준다."]
     /// # Arguments
-    /// * `index_schedule` - 인덱스 스케쥴 객체
+    /// * `index_name` - 인덱스(alias) 이름
+    /// * `index_schedules` - 해당 인덱스의 스케쥴 객체들
     ///
     /// # Returns
     /// * Result<(), anyhow::Error>
     pub async fn main_schedule_task(
         &self,
-        index_schedule: IndexSchedules,
+        index_name: &str,
+        index_schedules: Vec<IndexSchedules>,
     ) -> Result<(), anyhow::Error> {
-        let schedule: Schedule =
-            Schedule::from_str(&index_schedule.time).expect("Failed to parse CRON expression");
+        let mut entries: Vec<ScheduleEntry> = Vec::new();
 
-        let system_config: Arc<SystemConfig> = get_system_config();
+        for index_schedule in index_schedules {
+            let schedule: Schedule = index_schedule
+                .cron_schedule()
+                .map_err(|e| anyhow!("[Error][main_schedule_task()] {}: {}", index_name, e))?;
 
-        let mut interval: Interval = tokio::time::interval(tokio::time::Duration::from_millis(
-            system_config.schedule_term,
-        ));
+            let timezone: Tz = index_schedule
+                .cron_timezone()
+                .map_err(|e| anyhow!("[Error][main_schedule_task()] {}: {}", index_name, e))?;
 
-        /* 한국 표준시 GMT + 9 */
-        let kst_offset: FixedOffset = match FixedOffset::east_opt(9 * 3600) {
-            Some(kst_offset) => kst_offset,
-            None => {
-                error!(
-                    "[Error][main_schedule_task()] There was a problem initializing 'kst_offset'."
-                );
-                panic!(
-                    "[Error][main_schedule_task()] There was a problem initializing 'kst_offset'."
-                );
+            entries.push(ScheduleEntry::new(
+                index_schedule,
+                schedule,
+                timezone,
+                Utc::now(),
+            ));
+        }
+
+        /* 관리 API 에서 이 스케쥴러로 명령을 보낼 수 있도록 등록해준다. */
+        let (command_tx, mut command_rx) =
+            mpsc::channel::<SchedulerRequest>(SCHEDULER_COMMAND_CHANNEL_SIZE);
+        self.schedulers
+            .lock()
+            .await
+            .insert(index_name.to_string(), command_tx);
+
+        let mut running: Option<RunningTask<'_>> = None;
+        let mut pending: VecDeque<(IndexSchedules, RunTrigger)> = VecDeque::new();
+
+        /* 데몬이 내려가 있던 동안 놓친 실행은 놓친 시각 순서대로 대기열에 넣어준다. */
+        let mut missed_schedules: Vec<(DateTime<Utc>, IndexSchedules)> = Vec::new();
+
+        for entry in &entries {
+            match self
+                .find_missed_fire_time(&entry.index_schedule, &entry.schedule, &entry.timezone)
+                .await
+            {
+                Ok(Some(missed_fire)) => {
+                    missed_schedules.push((missed_fire, entry.index_schedule.clone()))
+                }
+                Ok(None) => (),
+                Err(e) => {
+                    error!(
+                        "[Error][main_schedule_task() -> find_missed_fire_time()] '{}' ({}) {:?}",
+                        index_name,
+                        entry.index_schedule.function_name(),
+                        e
+                    );
+                }
             }
-        };
+        }
+
+        missed_schedules.sort_by_key(|(missed_fire, _)| *missed_fire);
+
+        for (missed_fire, missed_schedule) in missed_schedules {
+            info!(
+                "[main_schedule_task()] '{}' ({}) missed the run at {}. It will catch up once.",
+                index_name,
+                missed_schedule.function_name(),
+                missed_fire
+            );
+            pending.push_back((missed_schedule, RunTrigger::CatchUp));
+        }
+
+        self.start_next_pending(&mut running, &mut pending);
 
         loop {
-            interval.tick().await;
+            /* 다음 실행 시각이 가장 빠른 스케쥴을 찾아준다. (모두 멈춘 경우 None) */
+            let next_run: Option<(usize, DateTime<Utc>)> = entries
+                .iter()
+                .enumerate()
+                .filter_map(|(idx, entry)| entry.next_fire().map(|next_fire| (idx, next_fire)))
+                .min_by_key(|(_, next_fire)| *next_fire);
 
-            let now: DateTime<Utc> = Utc::now();
-            let kst_now: DateTime<FixedOffset> = now.with_timezone(&kst_offset); /* Converting UTC Current Time to KST */
+            let schedule_statuses: Vec<ScheduleStatus> =
+                build_schedule_statuses(index_name, &entries, &running, &pending);
+            self.publish_schedule_statuses(index_name, schedule_statuses)
+                .await;
 
-            if let Some(next) = schedule.upcoming(kst_offset).take(1).next() {
-                if (next - kst_now).num_seconds() < 1 {
-                    match self.main_task(index_schedule.clone()).await {
-                        Ok(_) => (),
-                        Err(e) => {
-                            error!("[Error][main_schedule_task() -> main_task()] {:?}", e);
+            let sleep_duration: Duration = next_run
+                .and_then(|(_, next_fire)| (next_fire - Utc::now()).to_std().ok())
+                .unwrap_or_default();
+
+            tokio::select! {
+                _ = tokio::time::sleep(sleep_duration), if next_run.is_some() => {
+                    let (next_idx, next_fire) = match next_run {
+                        Some(next_run) => next_run,
+                        None => continue,
+                    };
+
+                    /* 마지막 실행 시각 기록 -> 지연으로 지나쳐버린 실행 시각은 건너뛴다. */
+                    entries[next_idx].last_fire = next_fire.max(Utc::now());
+
+                    let fired_schedule: IndexSchedules = entries[next_idx].index_schedule.clone();
+
+                    info!(
+                        "[main_schedule_task()] '{}' ({}) fired at {}",
+                        index_name,
+                        fired_schedule.function_name(),
+                        next_fire.with_timezone(&entries[next_idx].timezone)
+                    );
+
+                    self.dispatch_run(
+                        index_name,
+                        &mut running,
+                        &mut pending,
+                        fired_schedule,
+                        RunTrigger::Schedule,
+                    );
+                }
+                run_result = async {
+                    match running.as_mut() {
+                        Some((_, _, _, indexing_run)) => indexing_run.await,
+                        None => std::future::pending().await,
+                    }
+                } => {
+                    if let Some((finished_schedule, run_trigger, _, _)) = running.take() {
+                        match &run_result {
+                            Ok(run_report) => {
+                                info!(
+                                    "[main_schedule_task()] '{}' ({}) finished. {}",
+                                    index_name,
+                                    finished_schedule.function_name(),
+                                    run_report
+                                );
+                            }
+                            Err(e) if is_canceled_error(e) => {
+                                warn!(
+                                    "[main_schedule_task()] '{}' ({}) {:#}",
+                                    index_name,
+                                    finished_schedule.function_name(),
+                                    e
+                                );
+                            }
+                            Err(e) => {
+                                error!(
+                                    "[Error][main_schedule_task() -> main_task_with_retry()] '{}' ({}) {:?}",
+                                    index_name,
+                                    finished_schedule.function_name(),
+                                    e
+                                );
+                            }
+                        }
+
+                        if let Some(entry) = entries.iter_mut().find(|entry| {
+                            entry.index_schedule.function_name() == finished_schedule.function_name()
+                        }) {
+                            entry.last_result = Some(create_schedule_run_result(run_trigger, &run_result));
                         }
                     }
+
+                    self.start_next_pending(&mut running, &mut pending);
+                }
+                Some(request) = command_rx.recv() => {
+                    let command_result: Result<String, anyhow::Error> = self.handle_scheduler_command(
+                        index_name,
+                        request.command,
+                        &mut entries,
+                        &mut running,
+                        &mut pending,
+                    );
+
+                    /* 응답을 기다리던 요청이 끊긴 경우에도 명령은 이미 처리되었다. */
+                    let _ = request.reply.send(command_result);
                 }
             }
         }
     }
 
-    #[doc = "메인 작업 함수 -> 색인 진행 함수"]
+    #[doc = "스케쥴러에서 색인 작업을 시작하거나, 이미 실행중인 작업이 있으면 overlap_policy 에 따라 처리해주는 함수"]
     /// # Arguments
-    /// * `index_schedule` - 인덱스 스케쥴 객체
+    /// * `index_name` - 인덱스(alias) 이름
+    /// * `running` - 실행중인 작업
+    /// * `pending` - 대기중인 작업들
+    /// * `index_schedule` - 실행할 스케쥴 객체
+    /// * `run_trigger` - 색인 작업을 실행시킨 주체
     ///
     /// # Returns
-    /// * Result<(), anyhow::Error>
-    pub async fn main_task(&self, index_schedule: IndexSchedules) -> Result<(), anyhow::Error> {
-        let function_name: &str = index_schedule.function_name().as_str();
+    /// * RunDispatch
+    fn dispatch_run<'a>(
+        &'a self,
+        index_name: &str,
+        running: &mut Option<RunningTask<'a>>,
+        pending: &mut VecDeque<(IndexSchedules, RunTrigger)>,
+        index_schedule: IndexSchedules,
+        run_trigger: RunTrigger,
+    ) -> RunDispatch {
+        let running_function: String = match running.as_ref() {
+            Some((running_schedule, _, _, _)) => running_schedule.function_name().clone(),
+            None => {
+                *running = Some(self.create_running_task(index_schedule, run_trigger));
+                return RunDispatch::Started;
+            }
+        };
 
-        match function_name {
-            "store_static_index" => self.store_static_index(index_schedule).await?,
-            "store_dynamic_index" => self.store_dynamic_index(index_schedule).await?,
-            "auto_complete_static_index" => self.auto_complete_static_index(index_schedule).await?,
-            _ => {
-                return Err(anyhow!(
-                    "[Error][main_task()] The mapped function does not exist.: {}",
-                    function_name
-                ))
+        match index_schedule.overlap_policy() {
+            OverlapPolicy::Skip => {
+                warn!(
+                    "[dispatch_run()] '{}' ({}) is still running. '{}' is skipped.",
+                    index_name,
+                    running_function,
+                    index_schedule.function_name()
+                );
+                RunDispatch::Skipped
+            }
+            OverlapPolicy::Queue => {
+                /* 같은 작업이 이미 대기중이면 하나로 합쳐준다. */
+                if pending
+                    .iter()
+                    .any(|(queued, _)| queued.function_name() == index_schedule.function_name())
+                {
+                    warn!(
+                        "[dispatch_run()] '{}' ({}) is already queued.",
+                        index_name,
+                        index_schedule.function_name()
+                    );
+                    RunDispatch::AlreadyQueued
+                } else {
+                    info!(
+                        "[dispatch_run()] '{}' ({}) is still running. '{}' is queued.",
+                        index_name,
+                        running_function,
+                        index_schedule.function_name()
+                    );
+                    pending.push_back((index_schedule, run_trigger));
+                    RunDispatch::Queued
+                }
+            }
+            OverlapPolicy::Cancel => {
+                warn!(
+                    "[dispatch_run()] '{}' ({}) is canceled to run '{}'.",
+                    index_name,
+                    running_function,
+                    index_schedule.function_name()
+                );
+                /*
+                    실행중인 작업에 취소 신호를 보내고, 새로운 작업은 대기열 맨 앞에 넣어준다.
+                    이전 작업이 lock 해제와 실행 이력 기록을 마치고 끝나면 새로운 작업이 시작된다.
+                */
+                cancel_running_task(
+                    running,
+                    format!(
+                        "canceled by overlap_policy to run '{}'",
+                        index_schedule.function_name()
+                    ),
+                );
+                pending
+                    .retain(|(queued, _)| queued.function_name() != index_schedule.function_name());
+                pending.push_front((index_schedule, run_trigger));
+                RunDispatch::Replaced
             }
         }
+    }
 
-        Ok(())
+    #[doc = "대기중인 작업이 있고 실행중인 작업이 없으면 다음 작업을 시작해주는 함수"]
+    /// # Arguments
+    /// * `running` - 실행중인 작업
+    /// * `pending` - 대기중인 작업들
+    fn start_next_pending<'a>(
+        &'a self,
+        running: &mut Option<RunningTask<'a>>,
+        pending: &mut VecDeque<(IndexSchedules, RunTrigger)>,
+    ) {
+        if running.is_some() {
+            return;
+        }
+
+        if let Some((queued_schedule, run_trigger)) = pending.pop_front() {
+            *running = Some(self.create_running_task(queued_schedule, run_trigger));
+        }
     }
 
-    #[doc = ""]
+    #[doc = "스케쥴러에서 실행할 색인 작업을 만들어주는 함수"]
     /// # Arguments
-    /// * `store_seq` - 인덱스 스케쥴 객체
-    /// * `stores_distinct` - 중복을 제외한 store list
+    /// * `index_schedule` - 실행할 스케쥴 객체
+    /// * `run_trigger` - 색인 작업을 실행시킨 주체
     ///
     /// # Returns
-    /// * Result<(), anyhow::Error>
-    async fn handling_store_type(
+    /// * RunningTask
+    fn create_running_task(
         &self,
-        store_seq: Option<Vec<i32>>,
-        stores_distinct: &mut Vec<DistinctStoreResult>,
-    ) -> Result<(), anyhow::Error> {
-        /* store 리스트와 대응되는 소비분류 데이터 가져오기 */
-        let store_types_all: StoreTypesMap = if let Some(seq) = store_seq {
-            self.query_service.get_store_types(Some(seq)).await?
-        } else {
-            self.query_service.get_store_types(None).await?
-        };
+        index_schedule: IndexSchedules,
+        run_trigger: RunTrigger,
+    ) -> RunningTask<'_> {
+        let (cancel_tx, cancel_rx) = watch::channel::<Option<String>>(None);
 
-        let store_type_major_map: HashMap<i32, Vec<i32>> = store_types_all.store_type_major_map;
-        let store_type_sub_map: HashMap<i32, Vec<i32>> = store_types_all.store_type_sub_map;
+        (
+            index_schedule.clone(),
+            run_trigger,
+            cancel_tx,
+            Box::pin(self.main_task_with_retry(index_schedule, run_trigger, cancel_rx)),
+        )
+    }
 
-        for store_elem in stores_distinct {
-            let seq: i32 = store_elem.seq;
+    #[doc = "관리 API 에서 보낸 스케쥴러 명령을 처리해주는 함수"]
+    /// # Arguments
+    /// * `index_name` - 인덱스(alias) 이름
+    /// * `command` - 스케쥴러 명령
+    /// * `entries` - 스케쥴 상태들
+    /// * `running` - 실행중인 작업
+    /// * `pending` - 대기중인 작업들
+    ///
+    /// # Returns
+    /// * Result<String, anyhow::Error> - 처리 결과 메시지
+    fn handle_scheduler_command<'a>(
+        &'a self,
+        index_name: &str,
+        command: SchedulerCommand,
+        entries: &mut [ScheduleEntry],
+        running: &mut Option<RunningTask<'a>>,
+        pending: &mut VecDeque<(IndexSchedules, RunTrigger)>,
+    ) -> Result<String, anyhow::Error> {
+        info!(
+            "[handle_scheduler_command()] '{}' received {:?}",
+            index_name, command
+        );
 
-            let major_vec: &Vec<i32> = store_type_major_map
-                .get(&seq)
-                .ok_or_else(|| anyhow!("[Error][handling_store_type()] No 'seq' corresponding to 'store_type_major_map'. seq: {}", seq))?;
+        match command {
+            SchedulerCommand::Trigger(function_name) => {
+                let index_schedule: IndexSchedules = entries
+                    .iter()
+                    .find(|entry| *entry.index_schedule.function_name() == function_name)
+                    .map(|entry| entry.index_schedule.clone())
+                    .ok_or_else(|| {
+                        anyhow!(
+                            "[Error][handle_scheduler_command()] '{}' has no '{}' schedule.",
+                            index_name,
+                            function_name
+                        )
+                    })?;
 
-            let sub_vec: &Vec<i32> = store_type_sub_map
-                .get(&seq)
-                .ok_or_else(|| anyhow!("[Error][handling_store_type()] No 'seq' corresponding to 'store_type_sub_map'. seq: {}", seq))?;
+                let run_dispatch: RunDispatch = self.dispatch_run(
+                    index_name,
+                    running,
+                    pending,
+                    index_schedule,
+                    RunTrigger::Api,
+                );
 
-            store_elem.set_major_type(major_vec.clone());
-            store_elem.set_sub_type(sub_vec.clone());
+                Ok(format!(
+                    "'{}' ({}) {}",
+                    index_name,
+                    function_name,
+                    run_dispatch.as_str()
+                ))
+            }
+            SchedulerCommand::Cancel => {
+                /* 작업이 정리를 마치고 끝나면 스케쥴러 루프가 'canceled' 결과를 기록하고 다음 작업을 시작한다. */
+                let canceled_function: String =
+                    cancel_running_task(running, "canceled by the admin API".to_string())
+                        .ok_or_else(|| {
+                            anyhow!(
+                                "[Error][handle_scheduler_command()] '{}' has no run in progress.",
+                                index_name
+                            )
+                        })?;
+
+                warn!(
+                    "[handle_scheduler_command()] '{}' ({}) is canceled by the admin API.",
+                    index_name, canceled_function
+                );
+
+                Ok(format!(
+                    "'{}' ({}) is being canceled",
+                    index_name, canceled_function
+                ))
+            }
+            SchedulerCommand::Pause(function_name) => {
+                set_schedules_paused(index_name, entries, function_name, true)
+            }
+            SchedulerCommand::Resume(function_name) => {
+                set_schedules_paused(index_name, entries, function_name, false)
+            }
         }
+    }
 
-        Ok(())
+    #[doc = "관리 API 에서 조회할 수 있도록 스케쥴 상태를 갱신해주는 함수"]
+    /// # Arguments
+    /// * `index_name` - 인덱스(alias) 이름
+    /// * `schedule_statuses` - 해당 인덱스의 스케쥴 상태들
+    async fn publish_schedule_statuses(
+        &self,
+        index_name: &str,
+        schedule_statuses: Vec<ScheduleStatus>,
+    ) {
+        self.schedule_statuses
+            .lock()
+            .await
+            .insert(index_name.to_string(), schedule_statuses);
     }
 
-    #[doc = "Store 객체를 정적색인 해주는 함수"]
+    #[doc = "스케쥴 모드에서 관리중인 모든 스케쥴의 상태를 가져와주는 함수"]
+    /// # Returns
+    /// * Vec<ScheduleStatus>
+    pub async fn get_schedule_statuses(&self) -> Vec<ScheduleStatus> {
+        let schedule_statuses: MutexGuard<'_, HashMap<String, Vec<ScheduleStatus>>> =
+            self.schedule_statuses.lock().await;
+
+        let mut index_names: Vec<&String> = schedule_statuses.keys().collect();
+        index_names.sort();
+
+        index_names
+            .into_iter()
+            .flat_map(|index_name| schedule_statuses[index_name].iter().cloned())
+            .collect()
+    }
+
+    #[doc = "인덱스 스케쥴러로 명령을 보내고 처리 결과를 기다려주는 함수"]
     /// # Arguments
-    /// * `index_schedule` - 인덱스 스케쥴 객체
+    /// * `index_name` - 인덱스(alias) 이름
+    /// * `command` - 스케쥴러 명령
     ///
     /// # Returns
-    /// * Result<(), anyhow::Error>
-    async fn store_static_index(
+    /// * Result<Option<String>, anyhow::Error> - 해당 인덱스의 스케쥴러가 없으면 None
+    pub async fn send_scheduler_command(
         &self,
-        index_schedule: IndexSchedules,
-    ) -> Result<(), anyhow::Error> {
-        /* 현재기준 UTC 시간 */
-        let cur_utc_date: NaiveDateTime = get_current_utc_naive_datetime();
+        index_name: &str,
+        command: SchedulerCommand,
+    ) -> Result<Option<String>, anyhow::Error> {
+        let command_tx: mpsc::Sender<SchedulerRequest> =
+            match self.schedulers.lock().await.get(index_name) {
+                Some(command_tx) => command_tx.clone(),
+                None => return Ok(None),
+            };
 
-        /* 중복을 제외한 store 리스트 */
-        let mut stores_distinct: Vec<DistinctStoreResult> = self
-            .query_service
-            .get_all_store_table(&index_schedule, cur_utc_date)
-            .await?;
+        let (reply_tx, reply_rx) = oneshot::channel::<Result<String, anyhow::Error>>();
 
-        self.handling_store_type(None, &mut stores_distinct).await?;
+        command_tx
+            .send(SchedulerRequest::new(command, reply_tx))
+            .await
+            .map_err(|e| {
+                anyhow!(
+                    "[Error][send_scheduler_command()] '{}' scheduler is stopped.: {}",
+                    index_name,
+                    e
+                )
+            })?;
 
-        /* Elasticsearch 에 데이터 색인. */
-        self.es_query_service
-            .post_indexing_data_by_bulk_static::<DistinctStoreResult>(
-                &index_schedule,
-                &stores_distinct,
+        let command_result: String = reply_rx.await.map_err(|e| {
+            anyhow!(
+                "[Error][send_scheduler_command()] '{}' scheduler did not reply.: {}",
+                index_name,
+                e
             )
-            .await?;
+        })??;
 
-        /* 색인시간 최신화 */
-        self.query_service
-            .update_recent_date_to_elastic_index_info(&index_schedule, cur_utc_date)
-            .await?;
+        Ok(Some(command_result))
+    }
+
+    #[doc = "인덱스별 문서 수 일일 요약을 보내주는 스케쥴러 함수"]
+    /// # Arguments
+    /// * `index_aliases` - 요약 대상 인덱스(alias) 이름들
+    /// * `summary_schedule` - 일일 요약 cron 스케쥴
+    /// * `timezone` - cron 을 해석할 timezone
+    pub async fn daily_summary_task(
+        &self,
+        index_aliases: Vec<String>,
+        summary_schedule: Schedule,
+        timezone: Tz,
+    ) {
+        loop {
+            let next_fire: DateTime<Tz> = match summary_schedule.upcoming(timezone).next() {
+                Some(next_fire) => next_fire,
+                None => {
+                    warn!("[daily_summary_task()] There is no upcoming daily summary schedule.");
+                    return;
+                }
+            };
 
-        info!("Store - Static Create Indexing: {}", stores_distinct.len());
+            let sleep_duration: Duration = (next_fire.with_timezone(&Utc) - Utc::now())
+                .to_std()
+                .unwrap_or_default();
 
-        Ok(())
+            tokio::time::sleep(sleep_duration).await;
+
+            let mut doc_counts: Vec<(String, Result<u64, anyhow::Error>)> = Vec::new();
+
+            for index_alias in &index_aliases {
+                let doc_count: Result<u64, anyhow::Error> =
+                    self.es_query_service.get_index_doc_count(index_alias).await;
+
+                doc_counts.push((index_alias.clone(), doc_count));
+            }
+
+            self.notification_service
+                .notify_daily_summary(
+                    &next_fire.format("%Y-%m-%d %H:%M %Z").to_string(),
+                    &doc_counts,
+                )
+                .await;
+        }
     }
 
-    #[doc = "Store 객체를 증분색인 해주는 함수"]
+    #[doc = "마지막 성공 실행 이후 데몬이 놓친 가장 최근의 실행 시각을 찾아주는 함수"]
     /// # Arguments
     /// * `index_schedule` - 인덱스 스케쥴 객체
+    /// * `schedule` - cron 스케쥴
+    /// * `timezone` - cron 을 해석할 timezone
     ///
     /// # Returns
-    /// * Result<(), anyhow::Error>
-    async fn store_dynamic_index(
+    /// * Result<Option<DateTime<Utc>>, anyhow::Error> - 놓친 실행이 없거나 catch_up 정책이 none 이면 None
+    async fn find_missed_fire_time(
         &self,
-        index_schedule: IndexSchedules,
-    ) -> Result<(), anyhow::Error> {
-        let cur_utc_date: NaiveDateTime = get_current_utc_naive_datetime(); /* 현재기준 UTC 시간 */
+        index_schedule: &IndexSchedules,
+        schedule: &Schedule,
+        timezone: &Tz,
+    ) -> Result<Option<DateTime<Utc>>, anyhow::Error> {
+        if *index_schedule.catch_up() == CatchUpPolicy::None {
+            return Ok(None);
+        }
 
-        /* RDB 에서 검색엔진에 가장 마지막으로 색인한 날짜를 가져와준다. */
-        let recent_index_datetime: NaiveDateTime = self
+        let last_run: Option<elastic_index_run_history_tbl::Model> = self
             .query_service
-            .get_recent_date_from_elastic_index_info(&index_schedule)
+            .get_last_successful_run(index_schedule.index_name(), index_schedule.function_name())
             .await?;
 
-        /*
-            증분색인은 Delete -> Create 로 나눔
-            일단 수정되거나 새로 등록된 데이터를 기준으로 하는 상점 데이터를 모두 지워준다.
-            그 다음 Create 를 사용해서 update,create 된 모든 데이터를 실제로 색인해준다.
-        */
+        /* 성공 이력이 없는 경우에는 비교할 기준이 없으므로 다음 실행 시각을 기다린다. */
+        let last_run: elastic_index_run_history_tbl::Model = match last_run {
+            Some(last_run) => last_run,
+            None => {
+                info!(
+                    "[find_missed_fire_time()] '{}' ({}) has no successful run. Catch-up is skipped.",
+                    index_schedule.index_name(),
+                    index_schedule.function_name()
+                );
+                return Ok(None);
+            }
+        };
 
-        /* 0. 변경된 데이터 추출 */
-        let mut changed_list: Vec<DistinctStoreResult> = self
-            .query_service
-            .get_specific_store_table(&index_schedule, cur_utc_date, recent_index_datetime)
-            .await?;
+        let last_success: DateTime<Utc> = Utc.from_utc_datetime(&last_run.start_dt);
+        let now: DateTime<Utc> = Utc::now();
 
-        /* 1. Delete */
-        if !changed_list.is_empty() {
-            self.es_query_service
-                .delete_index(&index_schedule, &changed_list, "seq")
-                .await?;
-            info!("DELETE Data: {:?}", changed_list);
-        }
+        let missed_fire: Option<DateTime<Utc>> = schedule
+            .after(&last_success.with_timezone(timezone))
+            .map(|fire_time| fire_time.with_timezone(&Utc))
+            .take_while(|fire_time| *fire_time <= now)
+            .last();
 
-        /* 2. Create */
-        let seq_list: Vec<i32> = changed_list.iter().map(|item| item.seq).collect();
+        Ok(missed_fire)
+    }
 
-        self.handling_store_type(Some(seq_list), &mut changed_list)
-            .await?;
+    #[doc = "스케쥴 실행 함수 -> 실패 원인이 재시도 대상이면 backoff 만큼 기다린 뒤 다시 실행해준다."]
+    /// # Arguments
+    /// * `index_schedule` - 인덱스 스케쥴 객체
+    /// * `run_trigger` - 색인 작업을 실행시킨 주체
+    /// * `cancel_signal` - 색인 작업의 취소 신호
+    ///
+    /// # Returns
+    /// * Result<RunReport, anyhow::Error> - 마지막 시도의 결과
+    async fn main_task_with_retry(
+        &self,
+        index_schedule: IndexSchedules,
+        run_trigger: RunTrigger,
+        cancel_signal: CancelSignal,
+    ) -> Result<RunReport, anyhow::Error> {
+        let mut attempt: u32 = 1;
 
-        if !changed_list.is_empty() {
-            self.es_query_service
-                .post_indexing_data_by_bulk_dynamic::<DistinctStoreResult>(
-                    &index_schedule,
-                    &changed_list,
+        loop {
+            let error: anyhow::Error = match self
+                .main_task(
+                    index_schedule.clone(),
+                    run_trigger,
+                    attempt,
+                    cancel_signal.clone(),
                 )
-                .await?;
-            info!("CREATE Data: {:?}", changed_list);
+                .await
+            {
+                Ok(run_report) => {
+                    if attempt > 1 {
+                        info!(
+                            "[main_task_with_retry()] '{}' ({}) succeeded on attempt {}.",
+                            index_schedule.index_name(),
+                            index_schedule.function_name(),
+                            attempt
+                        );
+                    }
+                    return Ok(run_report);
+                }
+                Err(error) => error,
+            };
+
+            /* 취소된 작업은 재시도하거나 실패 알림을 보내지 않는다. */
+            if is_canceled_error(&error) {
+                return Err(error);
+            }
+
+            let error_class: RunErrorClass = RunErrorClass::classify(&error);
+
+            if !index_schedule.should_retry(attempt, error_class) {
+                /* 재시도 후에도 최종 실패한 경우에만 알림을 보낸다. */
+                self.notify_final_failure(&index_schedule, attempt, error_class, &error)
+                    .await;
+
+                return Err(error.context(format!(
+                    "[Error][main_task_with_retry()] '{}' ({}) gave up after {} attempt(s). ({})",
+                    index_schedule.index_name(),
+                    index_schedule.function_name(),
+                    attempt,
+                    error_class
+                )));
+            }
+
+            let retry_delay: Duration = index_schedule.retry_delay(attempt);
+
+            warn!(
+                "[main_task_with_retry()] '{}' ({}) attempt {}/{} failed ({}). Retry in {:?}.: {:#}",
+                index_schedule.index_name(),
+                index_schedule.function_name(),
+                attempt,
+                index_schedule.retry_max_attempts(),
+                error_class,
+                retry_delay,
+                error
+            );
+
+            tokio::select! {
+                _ = tokio::time::sleep(retry_delay) => (),
+                reason = wait_for_cancel(cancel_signal.clone()) => {
+                    return Err(IndexingError::Canceled { reason }.into());
+                }
+            }
+
+            attempt += 1;
         }
+    }
 
-        if !changed_list.is_empty() {
-            /* 색인시간 최신화 */
-            self.query_service
-                .update_recent_date_to_elastic_index_info(&index_schedule, cur_utc_date)
-                .await?;
+    #[doc = "최종 실패한 색인 작업의 알림을 보내주는 함수 -> 검증 실패는 alias 교체가 막혔다는 알림으로 보낸다."]
+    /// # Arguments
+    /// * `index_schedule` - 인덱스 스케쥴 객체
+    /// * `attempt` - 마지막 시도 횟수
+    /// * `error_class` - 실패 원인 분류
+    /// * `error` - 최종 에러
+    async fn notify_final_failure(
+        &self,
+        index_schedule: &IndexSchedules,
+        attempt: u32,
+        error_class: RunErrorClass,
+        error: &anyhow::Error,
+    ) {
+        match error_class {
+            RunErrorClass::Validation => {
+                self.notification_service
+                    .notify_validation_blocked(index_schedule, error)
+                    .await;
+            }
+            _ => {
+                self.notification_service
+                    .notify_run_failure(index_schedule, attempt, error_class, error)
+                    .await;
+            }
         }
+    }
 
-        Ok(())
+    #[doc = "CLI 로 실행한 색인 작업 함수 -> 재시도 없이 한번만 실행하고, 실패하면 스케쥴 실행과 같은 알림을 보낸다."]
+    /// # Arguments
+    /// * `index_schedule` - 인덱스 스케쥴 객체
+    ///
+    /// # Returns
+    /// * Result<RunReport, anyhow::Error>
+    async fn cli_main_task(
+        &self,
+        index_schedule: &IndexSchedules,
+    ) -> Result<RunReport, anyhow::Error> {
+        match self
+            .main_task(
+                index_schedule.clone(),
+                RunTrigger::Cli,
+                1,
+                create_uncancelable_signal(),
+            )
+            .await
+        {
+            Ok(run_report) => Ok(run_report),
+            Err(error) => {
+                let error_class: RunErrorClass = RunErrorClass::classify(&error);
+
+                self.notify_final_failure(index_schedule, 1, error_class, &error)
+                    .await;
+
+                Err(error)
+            }
+        }
     }
 
-    #[doc = "자동완성 키워드 정적색인 함수"]
-    pub async fn auto_complete_static_index(
+    #[doc = "메인 작업 함수 -> 색인을 진행하고 실행 이력을 남겨주는 함수"]
+    /// # Arguments
+    /// * `index_schedule` - 인덱스 스케쥴 객체
+    /// * `run_trigger` - 색인 작업을 실행시킨 주체
+    /// * `attempt` - 시도 횟수 (1부터 시작)
+    /// * `cancel_signal` - 색인 작업의 취소 신호
+    ///
+    /// # Returns
+    /// * Result<RunReport, anyhow::Error>
+    pub async fn main_task(
         &self,
         index_schedule: IndexSchedules,
-    ) -> Result<(), anyhow::Error> {
-        /* 현재기준 UTC 시간 */
-        //let cur_utc_date: NaiveDateTime = get_current_utc_naive_datetime();
+        run_trigger: RunTrigger,
+        attempt: u32,
+        cancel_signal: CancelSignal,
+    ) -> Result<RunReport, anyhow::Error> {
+        let start_dt: NaiveDateTime = get_current_utc_naive_datetime();
+        let started_at: Instant = Instant::now();
 
-        Ok(())
+        let index_name: String = index_schedule.index_name().clone();
+        let function_name: String = index_schedule.function_name().clone();
+
+        get_indexing_metrics().record_run_started(&index_name, &function_name);
+
+        /* 실행 이력을 남기지 못하더라도 색인 작업은 진행한다. */
+        let run_id: Option<i64> = match self
+            .query_service
+            .insert_run_history(&index_schedule, run_trigger, attempt, start_dt)
+            .await
+        {
+            Ok(run_id) => Some(run_id),
+            Err(e) => {
+                error!("[Error][main_task() -> insert_run_history()] {:?}", e);
+                None
+            }
+        };
+
+        let mut run_report: RunReport = RunReport::default();
+
+        let task_result: Result<(), anyhow::Error> = self
+            .run_with_index_lock(index_schedule, &mut run_report, cancel_signal)
+            .await;
+
+        let (run_status, error_class, error_message) = match &task_result {
+            Ok(_) => (RUN_STATUS_SUCCESS, None, None),
+            Err(e) if is_canceled_error(e) => (RUN_STATUS_CANCELED, None, Some(format!("{:#}", e))),
+            Err(e) => (
+                RUN_STATUS_FAILED,
+                Some(RunErrorClass::classify(e)),
+                Some(format!("{:#}", e)),
+            ),
+        };
+
+        if let Some(run_id) = run_id {
+            if let Err(e) = self
+                .query_service
+                .finish_run_history(
+                    run_id,
+                    &run_report,
+                    run_status,
+                    get_current_utc_naive_datetime(),
+                    error_class,
+                    error_message,
+                )
+                .await
+            {
+                error!("[Error][main_task() -> finish_run_history()] {:?}", e);
+            }
+        }
+
+        get_indexing_metrics().record_run_finished(
+            &index_name,
+            &function_name,
+            &run_report,
+            started_at.elapsed(),
+            run_status,
+            error_class,
+        );
+
+        task_result.map(|_| run_report)
     }
 
-    #[doc = "사용자의 입력을 받아서 색인을 진행시켜주는 함수"]
+    #[doc = "인덱스 lock 을 잡은 상태에서 색인을 진행해주는 함수"]
     /// # Arguments
-    /// * `index_schedules` - 인덱스 스케쥴 객체들
+    /// * `index_schedule` - 인덱스 스케쥴 객체
+    /// * `run_report` - 색인 작업 처리 결과
+    /// * `cancel_signal` - 색인 작업의 취소 신호
     ///
     /// # Returns
     /// * Result<(), anyhow::Error>
-    pub async fn cli_indexing_task(
+    async fn run_with_index_lock(
         &self,
-        index_schedules: IndexSchedulesConfig,
+        index_schedule: IndexSchedules,
+        run_report: &mut RunReport,
+        cancel_signal: CancelSignal,
     ) -> Result<(), anyhow::Error> {
-        let mut stdout: io::Stdout = io::stdout();
+        let index_name: String = index_schedule.index_name().clone();
+        let lease_secs: u64 = *index_schedule.lock_lease_secs();
 
-        let mut idx: i32 = 0;
+        /* 다른 인스턴스(스케쥴 데몬, CLI, 다른 pod)가 같은 인덱스를 색인중이면 실행하지 않는다. */
+        if !self
+            .query_service
+            .try_acquire_index_lock(&index_name, &self.lock_owner, lease_secs)
+            .await?
+        {
+            let lock_owner: String = match self.query_service.get_index_lock(&index_name).await {
+                Ok(Some(index_lock)) => index_lock.lock_owner,
+                Ok(None) => "-".to_string(),
+                Err(e) => {
+                    error!("[Error][run_with_index_lock() -> get_index_lock()] {:?}", e);
+                    "-".to_string()
+                }
+            };
 
-        writeln!(
-            stdout,
-            "[================ Yummy Indexing CLI ================]"
+            return Err(anyhow!(
+                "[Error][run_with_index_lock()] '{}' is being indexed by another instance.: {}",
+                index_name,
+                lock_owner
+            ));
+        }
+
+        /* heartbeat 가 lock 을 잃거나 취소 신호를 받으면 색인 작업도 중단된다. */
+        let task_result: Result<(), anyhow::Error> = tokio::select! {
+            biased;
+            reason = wait_for_cancel(cancel_signal) => Err(IndexingError::Canceled { reason }.into()),
+            lock_error = self.heartbeat_index_lock(&index_name, lease_secs) => Err(lock_error),
+            task_result = self.run_indexing_function(index_schedule, run_report) => task_result,
+        };
+
+        /* 취소된 정적색인이 만든 인덱스는 alias 교체 전이라면 lock 을 놓기 전에 지워준다. */
+        if let (Err(e), Some(generated_index_name)) =
+            (&task_result, run_report.generated_index_name.as_deref())
+        {
+            if is_canceled_error(e) {
+                match self
+                    .es_query_service
+                    .delete_index_generation(&index_name, generated_index_name)
+                    .await
+                {
+                    Ok(_) => info!(
+                        "[run_with_index_lock()] Deleted '{}' created by the canceled run.",
+                        generated_index_name
+                    ),
+                    Err(e) => warn!(
+                        "[run_with_index_lock() -> delete_index_generation()] '{}' is kept.: {:?}",
+                        generated_index_name, e
+                    ),
+                }
+            }
+        }
+
+        if let Err(e) = self
+            .query_service
+            .release_index_lock(&index_name, &self.lock_owner)
+            .await
+        {
+            error!(
+                "[Error][run_with_index_lock() -> release_index_lock()] {:?}",
+                e
+            );
+        }
+
+        task_result
+    }
+
+    #[doc = "function_name 에 맵핑된 색인 함수를 실행해주는 함수"]
+    /// # Arguments
+    /// * `index_schedule` - 인덱스 스케쥴 객체
+    /// * `run_report` - 색인 작업 처리 결과
+    ///
+    /// # Returns
+    /// * Result<(), anyhow::Error>
+    async fn run_indexing_function(
+        &self,
+        index_schedule: IndexSchedules,
+        run_report: &mut RunReport,
+    ) -> Result<(), anyhow::Error> {
+        let function_name: &str = index_schedule.function_name().as_str();
+
+        match function_name {
+            "store_static_index" => self.store_static_index(index_schedule, run_report).await?,
+            "store_dynamic_index" => self.store_dynamic_index(index_schedule, run_report).await?,
+            "auto_complete_static_index" => {
+                self.auto_complete_static_index(index_schedule, run_report)
+                    .await?
+            }
+            _ => {
+                return Err(anyhow!(
+                    "[Error][run_indexing_function()] The mapped function does not exist.: {}",
+                    function_name
+                ))
+            }
+        }
+
+        Ok(())
+    }
+
+    #[doc = "색인이 진행되는 동안 인덱스 lock 의 lease 를 주기적으로 연장해주는 함수"]
+    /// # Arguments
+    /// * `index_name` - 인덱스(alias) 이름
+    /// * `lease_secs` - lease 시간(초)
+    ///
+    /// # Returns
+    /// * anyhow::Error - lock 을 잃은 경우에만 반환된다.
+    async fn heartbeat_index_lock(&self, index_name: &str, lease_secs: u64) -> anyhow::Error {
+        let lease: Duration = Duration::from_secs(lease_secs);
+        let mut last_renewed_at: Instant = Instant::now();
+
+        loop {
+            tokio::time::sleep(lease / 3).await;
+
+            match self
+                .query_service
+                .renew_index_lock(index_name, &self.lock_owner, lease_secs)
+                .await
+            {
+                Ok(true) => last_renewed_at = Instant::now(),
+                Ok(false) => {
+                    return anyhow!(
+                        "[Error][heartbeat_index_lock()] The lock of '{}' was taken over by another instance.",
+                        index_name
+                    );
+                }
+                Err(e) => {
+                    /* 일시적인 DB 오류는 lease 가 만료되기 전까지 재시도한다. */
+                    if last_renewed_at.elapsed() >= lease {
+                        return anyhow!(
+                            "[Error][heartbeat_index_lock()] The lease of '{}' expired while renewing.: {:?}",
+                            index_name,
+                            e
+                        );
+                    }
+
+                    warn!(
+                        "[heartbeat_index_lock()] Failed to renew the lock of '{}'.: {:?}",
+                        index_name, e
+                    );
+                }
+            }
+        }
+    }
+
+    #[doc = "상점 리스트에 대분류/소분류 고유번호와 이름을 채워주는 함수"]
+    /// # Arguments
+    /// * `store_seq` - 분류 정보를 조회할 상점 고유번호 리스트 (None 인 경우 전체 조회)
+    /// * `stores_distinct` - 중복을 제외한 store list
+    ///
+    /// # Returns
+    /// * Result<(), anyhow::Error>
+    async fn handling_store_type(
+        &self,
+        store_seq: Option<Vec<i32>>,
+        stores_distinct: &mut Vec<DistinctStoreResult>,
+    ) -> Result<(), anyhow::Error> {
+        /* store 리스트와 대응되는 소비분류 데이터 가져오기 */
+        let store_types_all: StoreTypesMap = if let Some(seq) = store_seq {
+            self.query_service.get_store_types(Some(seq)).await?
+        } else {
+            self.query_service.get_store_types(None).await?
+        };
+
+        let store_type_major_map: HashMap<i32, Vec<i32>> = store_types_all.store_type_major_map;
+        let store_type_sub_map: HashMap<i32, Vec<i32>> = store_types_all.store_type_sub_map;
+        let store_type_major_name_map: HashMap<i32, Vec<String>> =
+            store_types_all.store_type_major_name_map;
+        let store_type_sub_name_map: HashMap<i32, Vec<String>> =
+            store_types_all.store_type_sub_name_map;
+
+        for store_elem in stores_distinct {
+            let seq: i32 = store_elem.seq;
+
+            let major_vec: &Vec<i32> = store_type_major_map
+                .get(&seq)
+                .ok_or_else(|| anyhow!("[Error][handling_store_type()] No 'seq' corresponding to 'store_type_major_map'. seq: {}", seq))?;
+
+            let sub_vec: &Vec<i32> = store_type_sub_map
+                .get(&seq)
+                .ok_or_else(|| anyhow!("[Error][handling_store_type()] No 'seq' corresponding to 'store_type_sub_map'. seq: {}", seq))?;
+
+            /* 이름 맵은 고유번호 맵과 같은 순서로 채워지므로 고유번호가 있으면 이름도 존재한다. */
+            let major_name_vec: Vec<String> = store_type_major_name_map
+                .get(&seq)
+                .cloned()
+                .unwrap_or_default();
+
+            let sub_name_vec: Vec<String> = store_type_sub_name_map
+                .get(&seq)
+                .cloned()
+                .unwrap_or_default();
+
+            store_elem.set_major_type(major_vec.clone());
+            store_elem.set_sub_type(sub_vec.clone());
+            store_elem.set_major_type_name(major_name_vec);
+            store_elem.set_sub_type_name(sub_name_vec);
+        }
+
+        Ok(())
+    }
+
+    #[doc = "MySQL 에서 읽은 상점 페이지를 분류 정보로 채워서 곧바로 bulk 색인해주는 함수"]
+    /// # Arguments
+    /// * `index_schedule` - 인덱스 스케쥴 객체
+    /// * `index_name` - 색인 대상 인덱스 (또는 alias) 이름
+    /// * `cur_utc_date` - 현재 UTC 기준 시간 데이터
+    /// * `recent_datetime` - 가장 최근 색인 시각 (None 인 경우 전체 상점을 색인)
+    ///
+    /// # Returns
+    /// * Result<IndexingStats, anyhow::Error> - 읽기/쓰기 단계별 처리 통계
+    async fn stream_store_to_index(
+        &self,
+        index_schedule: &IndexSchedules,
+        index_name: &str,
+        cur_utc_date: NaiveDateTime,
+        recent_datetime: Option<NaiveDateTime>,
+    ) -> Result<IndexingStats, anyhow::Error> {
+        /*
+            읽기 단계(sql_batch_size)와 쓰기 단계(es_batch_size, es_max_bulk_bytes)를 크기가 정해진 채널로 연결해준다.
+            채널이 가득 차면 읽기 단계는 쓰기 단계가 페이지를 가져갈 때까지 대기하므로
+            메모리에는 채널에 쌓인 페이지와 쓰기 단계의 버퍼만 올라간다.
+        */
+        let (page_sender, mut page_receiver) =
+            mpsc::channel::<Vec<DistinctStoreResult>>(STORE_PAGE_CHANNEL_SIZE);
+
+        /* 1. 읽기 단계: 상점 단위로 잘린 페이지를 중복제거하고 분류 정보를 채워서 넘겨준다. */
+        let read_stage = async move {
+            let mut read_stats: StageStats = StageStats::default();
+            let mut last_seq: Option<i32> = None;
+
+            loop {
+                let started_at: Instant = Instant::now();
+
+                let mut store_page: Vec<DistinctStoreResult> = match recent_datetime {
+                    Some(recent_datetime) => {
+                        self.query_service
+                            .get_specific_store_table(
+                                index_schedule,
+                                cur_utc_date,
+                                recent_datetime,
+                                last_seq,
+                            )
+                            .await?
+                    }
+                    None => {
+                        self.query_service
+                            .get_all_store_table(index_schedule, cur_utc_date, last_seq)
+                            .await?
+                    }
+                };
+
+                match store_page.last() {
+                    Some(store) => last_seq = Some(store.seq),
+                    None => break,
+                }
+
+                let seq_list: Vec<i32> = store_page.iter().map(|store| store.seq).collect();
+
+                self.handling_store_type(Some(seq_list), &mut store_page)
+                    .await?;
+
+                read_stats.record_batch(store_page.len(), 0, started_at);
+
+                /* 쓰기 단계가 먼저 실패하여 종료된 경우 더 읽을 필요가 없다. */
+                if page_sender.send(store_page).await.is_err() {
+                    break;
+                }
+            }
+
+            Ok::<StageStats, anyhow::Error>(read_stats)
+        };
+
+        /* 2. 쓰기 단계: 페이지를 es_batch_size 만큼 모아서 bulk 색인해준다. */
+        let write_stage = async move {
+            let es_batch_size: usize = *index_schedule.es_batch_size();
+
+            let mut write_stats: StageStats = StageStats::default();
+            let mut document_buffer: Vec<DistinctStoreResult> = Vec::new();
+
+            while let Some(mut store_page) = page_receiver.recv().await {
+                document_buffer.append(&mut store_page);
+
+                while document_buffer.len() >= es_batch_size {
+                    let documents: Vec<DistinctStoreResult> =
+                        document_buffer.drain(..es_batch_size).collect();
+
+                    self.flush_store_documents(
+                        index_schedule,
+                        index_name,
+                        &documents,
+                        &mut write_stats,
+                    )
+                    .await?;
+                }
+            }
+
+            if !document_buffer.is_empty() {
+                self.flush_store_documents(
+                    index_schedule,
+                    index_name,
+                    &document_buffer,
+                    &mut write_stats,
+                )
+                .await?;
+            }
+
+            Ok::<StageStats, anyhow::Error>(write_stats)
+        };
+
+        /* 어느 한 단계가 실패하면 다른 단계도 함께 중단된다. */
+        let (read_stats, write_stats) = tokio::try_join!(read_stage, write_stage)?;
+
+        Ok(IndexingStats {
+            read_stage: read_stats,
+            write_stage: write_stats,
+        })
+    }
+
+    #[doc = "쓰기 단계에 모인 상점 문서들을 bulk 색인하고 통계를 남겨주는 함수"]
+    /// # Arguments
+    /// * `index_schedule` - 인덱스 스케쥴 객체
+    /// * `index_name` - 색인 대상 인덱스 (또는 alias) 이름
+    /// * `documents` - 색인할 상점 문서들
+    /// * `write_stats` - 쓰기 단계 통계
+    ///
+    /// # Returns
+    /// * Result<(), anyhow::Error>
+    async fn flush_store_documents(
+        &self,
+        index_schedule: &IndexSchedules,
+        index_name: &str,
+        documents: &[DistinctStoreResult],
+        write_stats: &mut StageStats,
+    ) -> Result<(), anyhow::Error> {
+        let started_at: Instant = Instant::now();
+
+        let failed_cnt: usize = self
+            .es_query_service
+            .post_indexing_data_by_bulk::<DistinctStoreResult>(
+                index_schedule,
+                index_name,
+                documents,
+            )
+            .await?;
+
+        write_stats.record_batch(documents.len(), failed_cnt, started_at);
+
+        Ok(())
+    }
+
+    #[doc = "Store 객체를 정적색인 해주는 함수"]
+    /// # Arguments
+    /// * `index_schedule` - 인덱스 스케쥴 객체
+    /// * `run_report` - 색인 작업 처리 결과
+    ///
+    /// # Returns
+    /// * Result<(), anyhow::Error>
+    async fn store_static_index(
+        &self,
+        index_schedule: IndexSchedules,
+        run_report: &mut RunReport,
+    ) -> Result<(), anyhow::Error> {
+        /* 현재기준 UTC 시간 */
+        let cur_utc_date: NaiveDateTime = get_current_utc_naive_datetime();
+
+        /* 새로운 세대의 인덱스를 만들고, 상점 정보를 페이지 단위로 읽어서 바로 색인해준다. */
+        let new_index_name: String = self
+            .es_query_service
+            .create_static_index(&index_schedule)
+            .await?;
+
+        run_report.generated_index_name = Some(new_index_name.clone());
+
+        let indexing_stats: IndexingStats = self
+            .stream_store_to_index(&index_schedule, &new_index_name, cur_utc_date, None)
+            .await?;
+
+        info!("Store - Static Indexing stats: {}", indexing_stats);
+
+        let write_stats: &StageStats = indexing_stats.write_stage();
+
+        run_report.rows_read = *indexing_stats.read_stage().document_cnt();
+        run_report.documents_written = write_stats.document_cnt() - write_stats.failed_cnt();
+        run_report.documents_failed = *write_stats.failed_cnt();
+
+        index_schedule.check_bulk_failure_ratio(
+            &new_index_name,
+            *write_stats.failed_cnt(),
+            *write_stats.document_cnt(),
+        )?;
+
+        /* 검증을 통과한 경우에만 alias 를 새로운 인덱스로 교체해준다. */
+        self.es_query_service
+            .swap_static_index_alias(
+                &index_schedule,
+                &new_index_name,
+                *indexing_stats.read_stage().document_cnt(),
+            )
+            .await?;
+
+        /* 색인시간 최신화 */
+        self.query_service
+            .update_recent_date_to_elastic_index_info(&index_schedule, cur_utc_date)
+            .await?;
+
+        info!(
+            "Store - Static Create Indexing: {}",
+            write_stats.document_cnt()
+        );
+
+        Ok(())
+    }
+
+    #[doc = "Store 객체를 증분색인 해주는 함수"]
+    /// # Arguments
+    /// * `index_schedule` - 인덱스 스케쥴 객체
+    /// * `run_report` - 색인 작업 처리 결과
+    ///
+    /// # Returns
+    /// * Result<(), anyhow::Error>
+    async fn store_dynamic_index(
+        &self,
+        index_schedule: IndexSchedules,
+        run_report: &mut RunReport,
+    ) -> Result<(), anyhow::Error> {
+        let cur_utc_date: NaiveDateTime = get_current_utc_naive_datetime(); /* 현재기준 UTC 시간 */
+
+        /* RDB 에서 검색엔진에 가장 마지막으로 색인한 날짜를 가져와준다. */
+        let recent_index_datetime: NaiveDateTime = self
+            .query_service
+            .get_recent_date_from_elastic_index_info(&index_schedule)
+            .await?;
+
+        /* 마지막 색인 시각이 허용치보다 뒤쳐져 있으면 알림을 보내준다. (색인은 그대로 진행) */
+        if let Some(watermark_lag_alert_secs) = index_schedule.watermark_lag_alert_secs() {
+            let lag_secs: i64 = (cur_utc_date - recent_index_datetime).num_seconds();
+
+            if lag_secs > *watermark_lag_alert_secs as i64 {
+                warn!(
+                    "[store_dynamic_index()] '{}' is {}s behind its watermark. ({})",
+                    index_schedule.index_name(),
+                    lag_secs,
+                    get_str_from_naive_datetime(recent_index_datetime)
+                );
+
+                self.notification_service
+                    .notify_watermark_lag(&index_schedule, recent_index_datetime, lag_secs)
+                    .await;
+            }
+        }
+
+        /*
+            증분색인은 Upsert -> Delete 로 나눔
+            문서의 _id 를 상점 고유번호(seq)로 색인하므로, 수정되거나 새로 등록된 데이터는
+            bulk index 한번으로 기존 문서를 덮어써준다. (검색에서 상점이 사라지는 구간이 없음)
+            그 다음 비활성화되거나 삭제된 상점 데이터를 인덱스에서 제거해준다.
+        */
+
+        /* 1. Upsert -> 변경된 데이터를 페이지 단위로 읽어서 바로 색인 */
+        let indexing_stats: IndexingStats = self
+            .stream_store_to_index(
+                &index_schedule,
+                index_schedule.index_name(),
+                cur_utc_date,
+                Some(recent_index_datetime),
+            )
+            .await?;
+
+        info!("Store - Dynamic Indexing stats: {}", indexing_stats);
+
+        let changed_cnt: usize = *indexing_stats.write_stage().document_cnt();
+
+        run_report.rows_read = *indexing_stats.read_stage().document_cnt();
+        run_report.documents_written = changed_cnt - indexing_stats.write_stage().failed_cnt();
+        run_report.documents_failed = *indexing_stats.write_stage().failed_cnt();
+
+        index_schedule.check_bulk_failure_ratio(
+            index_schedule.index_name(),
+            *indexing_stats.write_stage().failed_cnt(),
+            changed_cnt,
+        )?;
+
+        /*
+            2. 비활성화(use_yn = 'N') 된 상점 제거
+            변경분 쿼리는 use_yn = 'Y' 인 데이터만 가져오므로, 마지막 색인 이후 비활성화된 상점만 따로 조회해서 지워준다.
+        */
+        let removed_seqs: Vec<i32> = self
+            .query_service
+            .get_removed_store_seqs(recent_index_datetime)
+            .await?;
+
+        if !removed_seqs.is_empty() {
+            self.es_query_service
+                .delete_index_by_field_values(&index_schedule, &removed_seqs, "seq")
+                .await?;
+            info!("REMOVE Data: {:?}", removed_seqs);
+        }
+
+        run_report.documents_deleted = removed_seqs.len();
+
+        info!(
+            "Store - Dynamic Indexing: changed {}, removed {}",
+            changed_cnt,
+            removed_seqs.len()
+        );
+
+        if changed_cnt > 0 || !removed_seqs.is_empty() {
+            /* 색인시간 최신화 */
+            self.query_service
+                .update_recent_date_to_elastic_index_info(&index_schedule, cur_utc_date)
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    #[doc = "상점 페이지로부터 자동완성 키워드를 모아주는 함수 -> 같은 키워드는 하나로 합쳐진다."]
+    /// # Arguments
+    /// * `keyword_map` - 키워드 -> (키워드를 가진 상점 고유번호 집합, 키워드 출처 집합)
+    /// * `stores_distinct` - 중복을 제외한 store list
+    /// * `store_types_all` - 상점별 대분류/소분류 정보
+    fn collect_auto_complete_keywords(
+        &self,
+        keyword_map: &mut HashMap<String, (HashSet<i32>, BTreeSet<&'static str>)>,
+        stores_distinct: &[DistinctStoreResult],
+        store_types_all: &StoreTypesMap,
+    ) {
+        for store in stores_distinct {
+            let mut keywords: Vec<(&str, &'static str)> =
+                vec![(store.name.as_str(), SOURCE_STORE_NAME)];
+
+            keywords.extend(
+                store
+                    .recommend_names
+                    .iter()
+                    .map(|recommend_name| (recommend_name.as_str(), SOURCE_RECOMMEND_NAME)),
+            );
+
+            for type_name_map in [
+                &store_types_all.store_type_major_name_map,
+                &store_types_all.store_type_sub_name_map,
+            ] {
+                if let Some(type_names) = type_name_map.get(&store.seq) {
+                    keywords.extend(
+                        type_names
+                            .iter()
+                            .map(|type_name| (type_name.as_str(), SOURCE_STORE_TYPE)),
+                    );
+                }
+            }
+
+            keywords.extend(
+                [
+                    &store.location_city,
+                    &store.location_county,
+                    &store.location_district,
+                ]
+                .into_iter()
+                .flatten()
+                .map(|location| (location.as_str(), SOURCE_LOCATION)),
+            );
+
+            for (keyword, source_type) in keywords {
+                let keyword: &str = keyword.trim();
+
+                if keyword.is_empty() {
+                    continue;
+                }
+
+                let (store_seqs, source_types) = keyword_map
+                    .entry(keyword.to_string())
+                    .or_insert_with(|| (HashSet::new(), BTreeSet::new()));
+
+                store_seqs.insert(store.seq);
+                source_types.insert(source_type);
+            }
+        }
+    }
+
+    #[doc = "자동완성 키워드 정적색인 함수"]
+    /// # Arguments
+    /// * `index_schedule` - 인덱스 스케쥴 객체
+    /// * `run_report` - 색인 작업 처리 결과
+    ///
+    /// # Returns
+    /// * Result<(), anyhow::Error>
+    pub async fn auto_complete_static_index(
+        &self,
+        index_schedule: IndexSchedules,
+        run_report: &mut RunReport,
+    ) -> Result<(), anyhow::Error> {
+        /* 현재기준 UTC 시간 */
+        let cur_utc_date: NaiveDateTime = get_current_utc_naive_datetime();
+
+        let cur_time_utc: String = get_str_from_naive_datetime(cur_utc_date);
+
+        /* 상점 정보를 페이지 단위로 읽으면서 키워드만 모아준다. */
+        let mut keyword_map: HashMap<String, (HashSet<i32>, BTreeSet<&'static str>)> =
+            HashMap::new();
+        let mut last_seq: Option<i32> = None;
+
+        loop {
+            let stores_distinct: Vec<DistinctStoreResult> = self
+                .query_service
+                .get_all_store_table(&index_schedule, cur_utc_date, last_seq)
+                .await?;
+
+            let seq_list: Vec<i32> = match stores_distinct.last() {
+                Some(store) => {
+                    last_seq = Some(store.seq);
+                    stores_distinct.iter().map(|store| store.seq).collect()
+                }
+                None => break,
+            };
+
+            run_report.rows_read += stores_distinct.len();
+
+            /* 상점별 대분류/소분류 이름 */
+            let store_types_all: StoreTypesMap =
+                self.query_service.get_store_types(Some(seq_list)).await?;
+
+            self.collect_auto_complete_keywords(
+                &mut keyword_map,
+                &stores_distinct,
+                &store_types_all,
+            );
+        }
+
+        let auto_complete_keywords: Vec<AutoCompleteKeyword> = keyword_map
+            .into_iter()
+            .map(|(keyword, (store_seqs, source_types))| {
+                AutoCompleteKeyword::new(
+                    cur_time_utc.clone(),
+                    keyword,
+                    store_seqs.len(), /* 키워드를 공유하는 상점 수를 인기도 가중치로 사용한다. */
+                    source_types.into_iter().map(String::from).collect(),
+                )
+            })
+            .collect();
+
+        /* Elasticsearch 에 데이터 색인. */
+        let new_index_name: String = self
+            .es_query_service
+            .create_static_index(&index_schedule)
+            .await?;
+
+        run_report.generated_index_name = Some(new_index_name.clone());
+
+        let failed_cnt: usize = self
+            .es_query_service
+            .post_indexing_data_by_bulk::<AutoCompleteKeyword>(
+                &index_schedule,
+                &new_index_name,
+                &auto_complete_keywords,
+            )
+            .await?;
+
+        run_report.documents_written = auto_complete_keywords.len() - failed_cnt;
+        run_report.documents_failed = failed_cnt;
+
+        index_schedule.check_bulk_failure_ratio(
+            &new_index_name,
+            failed_cnt,
+            auto_complete_keywords.len(),
+        )?;
+
+        self.es_query_service
+            .swap_static_index_alias(
+                &index_schedule,
+                &new_index_name,
+                auto_complete_keywords.len(),
+            )
+            .await?;
+
+        /* 색인시간 최신화 */
+        self.query_service
+            .update_recent_date_to_elastic_index_info(&index_schedule, cur_utc_date)
+            .await?;
+
+        info!(
+            "AutoComplete - Static Create Indexing: {}",
+            auto_complete_keywords.len()
+        );
+
+        Ok(())
+    }
+
+    #[doc = "사용자의 입력을 받아서 색인을 진행시켜주는 함수"]
+    /// # Arguments
+    /// * `index_schedules` - 인덱스 스케쥴 객체들
+    ///
+    /// # Returns
+    /// * Result<(), anyhow::Error>
+    pub async fn cli_indexing_task(
+        &self,
+        index_schedules: IndexSchedulesConfig,
+    ) -> Result<(), anyhow::Error> {
+        let mut stdout: io::Stdout = io::stdout();
+
+        let mut idx: i32 = 0;
+
+        writeln!(
+            stdout,
+            "[================ Yummy Indexing CLI ================]"
         )
         .unwrap();
         writeln!(stdout, "Select the index you want to perform.").unwrap();
@@ -296,8 +1591,10 @@ impl<Q: QueryService, E: EsQueryService> MainController<Q, E> {
                             index_schedules.index().get((number - 1) as usize).unwrap();
 
                         /* 여기서 색인 작업을 진행해준다. */
-                        match self.main_task(index.clone()).await {
-                            Ok(_) => (),
+                        match self.cli_main_task(index).await {
+                            Ok(run_report) => {
+                                writeln!(stdout, "{}", run_report).unwrap();
+                            }
                             Err(e) => {
                                 error!("[Error][cli_indexing_task() -> main_task()] {:?}", e);
                                 writeln!(stdout, "Index failed.").unwrap();
@@ -324,4 +1621,1147 @@ impl<Q: QueryService, E: EsQueryService> MainController<Q, E> {
 
         Ok(())
     }
+
+    #[doc = "사용자의 입력을 받아서 alias 를 이전 세대의 인덱스로 롤백시켜주는 함수"]
+    /// # Arguments
+    /// * `index_schedules` - 인덱스 스케쥴 객체들
+    ///
+    /// # Returns
+    /// * Result<(), anyhow::Error>
+    pub async fn cli_rollback_task(
+        &self,
+        index_schedules: IndexSchedulesConfig,
+    ) -> Result<(), anyhow::Error> {
+        let mut stdout: io::Stdout = io::stdout();
+
+        /* 정적색인 대상 alias 만 세대를 가진다. */
+        let mut index_aliases: Vec<&String> = index_schedules
+            .index()
+            .iter()
+            .filter(|index| index.setting_path().is_some())
+            .map(|index| index.index_name())
+            .collect();
+        index_aliases.sort();
+        index_aliases.dedup();
+
+        if index_aliases.is_empty() {
+            writeln!(stdout, "There is no static index to roll back.")?;
+            return Ok(());
+        }
+
+        writeln!(
+            stdout,
+            "[================ Yummy Index Rollback ================]"
+        )?;
+        writeln!(stdout, "Select the index alias you want to roll back.")?;
+
+        for (idx, index_alias) in index_aliases.iter().enumerate() {
+            writeln!(stdout, "[{}] {}", idx + 1, index_alias)?;
+        }
+
+        let alias_number: usize = read_user_input("\nPlease enter your number: ")?
+            .parse::<usize>()
+            .map_err(|e| anyhow!("[Error][cli_rollback_task()] Invalid input.: {:?}", e))?;
+
+        let index_alias: &String =
+            index_aliases
+                .get(alias_number.wrapping_sub(1))
+                .ok_or_else(|| {
+                    anyhow!(
+                        "[Error][cli_rollback_task()] Please enter a number between 1 and {}.",
+                        index_aliases.len()
+                    )
+                })?;
+
+        let generations: Vec<IndexGeneration> = self
+            .es_query_service
+            .get_index_generations(index_alias)
+            .await?;
+
+        if generations.is_empty() {
+            writeln!(stdout, "There is no generation of '{}'.", index_alias)?;
+            return Ok(());
+        }
+
+        print_index_generations(&mut stdout, index_alias, &generations)?;
+
+        let generation_number: usize = read_user_input("\nPlease enter your number: ")?
+            .parse::<usize>()
+            .map_err(|e| anyhow!("[Error][cli_rollback_task()] Invalid input.: {:?}", e))?;
+
+        let target: &IndexGeneration = generations
+            .get(generation_number.wrapping_sub(1))
+            .ok_or_else(|| {
+                anyhow!(
+                    "[Error][cli_rollback_task()] Please enter a number between 1 and {}.",
+                    generations.len()
+                )
+            })?;
+
+        if *target.is_alias_target() {
+            writeln!(
+                stdout,
+                "'{}' is already mapped to '{}'.",
+                target.index_name(),
+                index_alias
+            )?;
+            return Ok(());
+        }
+
+        if !confirm_user_input(
+            &format!(
+                "Point '{}' to '{}'? [y/N]: ",
+                index_alias,
+                target.index_name()
+            ),
+            false,
+        )? {
+            writeln!(stdout, "Rollback canceled.")?;
+            return Ok(());
+        }
+
+        self.es_query_service
+            .rollback_index_alias(index_alias, target.index_name())
+            .await?;
+
+        info!(
+            "[cli_rollback_task()] '{}' was rolled back to '{}'",
+            index_alias,
+            target.index_name()
+        );
+        writeln!(stdout, "Rollback operation completed.")?;
+
+        Ok(())
+    }
+
+    #[doc = "커맨드라인 서브커맨드를 처리해주는 함수 -> 스크립트/cron 에서 호출하기 위한 용도"]
+    /// # Arguments
+    /// * `command` - 실행할 서브커맨드
+    /// * `index_schedules` - 인덱스 스케쥴 객체들
+    /// * `assume_yes` - 확인 프롬프트를 생략할지 여부
+    ///
+    /// # Returns
+    /// * Result<(), anyhow::Error> - 실패하는 경우 프로세스는 실패 exit code 로 종료된다.
+    pub async fn cli_command_task(
+        &self,
+        command: CliCommand,
+        index_schedules: IndexSchedulesConfig,
+        assume_yes: bool,
+    ) -> Result<(), anyhow::Error> {
+        let mut stdout: io::Stdout = io::stdout();
+
+        match command {
+            CliCommand::Run {
+                index,
+                indexing_type,
+            } => {
+                let index_schedule: &IndexSchedules = index_schedules
+                    .index()
+                    .iter()
+                    .find(|schedule| {
+                        *schedule.index_name() == index
+                            && schedule.indexing_type() == indexing_type.as_str()
+                    })
+                    .ok_or_else(|| {
+                        anyhow!(
+                            "[Error][cli_command_task()] No '{}' index of '{}' type in the index list.",
+                            index,
+                            indexing_type.as_str()
+                        )
+                    })?;
+
+                if indexing_type == CliIndexingType::Static
+                    && !confirm_user_input(
+                        &format!("Rebuild '{}' and swap the alias? [y/N]: ", index),
+                        assume_yes,
+                    )?
+                {
+                    writeln!(stdout, "Indexing canceled.")?;
+                    return Ok(());
+                }
+
+                let run_report: RunReport = self.cli_main_task(index_schedule).await?;
+                writeln!(stdout, "{}", run_report)?;
+                writeln!(stdout, "Indexing operation completed.")?;
+            }
+            CliCommand::List => {
+                for (idx, schedule) in index_schedules.index().iter().enumerate() {
+                    writeln!(
+                        stdout,
+                        "[{}] {} | {} | {} | cron: {} ({})",
+                        idx + 1,
+                        schedule.index_name(),
+                        schedule.indexing_type(),
+                        schedule.function_name(),
+                        schedule.time(),
+                        schedule.timezone().as_deref().unwrap_or(DEFAULT_TIMEZONE)
+                    )?;
+                }
+            }
+            CliCommand::Status => {
+                let mut seen_aliases: HashSet<&String> = HashSet::new();
+
+                for schedule in index_schedules.index() {
+                    if !seen_aliases.insert(schedule.index_name()) {
+                        continue;
+                    }
+
+                    let doc_count: String = match self
+                        .es_query_service
+                        .get_index_doc_count(schedule.index_name())
+                        .await
+                    {
+                        Ok(doc_count) => doc_count.to_string(),
+                        Err(e) => {
+                            error!(
+                                "[Error][cli_command_task() -> get_index_doc_count()] {:?}",
+                                e
+                            );
+                            "-".to_string()
+                        }
+                    };
+
+                    let current_index: String = match self
+                        .es_query_service
+                        .get_index_generations(schedule.index_name())
+                        .await
+                    {
+                        Ok(generations) => generations
+                            .into_iter()
+                            .find(|generation| generation.is_alias_target)
+                            .map(|generation| generation.index_name)
+                            .unwrap_or_else(|| "-".to_string()),
+                        Err(e) => {
+                            error!(
+                                "[Error][cli_command_task() -> get_index_generations()] {:?}",
+                                e
+                            );
+                            "-".to_string()
+                        }
+                    };
+
+                    let watermark: String = match self
+                        .query_service
+                        .get_recent_date_from_elastic_index_info(schedule)
+                        .await
+                    {
+                        Ok(watermark) => get_str_from_naive_datetime(watermark),
+                        Err(e) => {
+                            error!("[Error][cli_command_task() -> get_recent_date_from_elastic_index_info()] {:?}", e);
+                            "-".to_string()
+                        }
+                    };
+
+                    let lock_state: String = match self
+                        .query_service
+                        .get_index_lock(schedule.index_name())
+                        .await
+                    {
+                        Ok(Some(index_lock)) => format!(
+                            "{} (until {})",
+                            index_lock.lock_owner,
+                            get_str_from_naive_datetime(index_lock.lock_expire_dt)
+                        ),
+                        Ok(None) => "-".to_string(),
+                        Err(e) => {
+                            error!("[Error][cli_command_task() -> get_index_lock()] {:?}", e);
+                            "-".to_string()
+                        }
+                    };
+
+                    writeln!(
+                        stdout,
+                        "{} | index: {} | docs: {} | watermark(UTC): {} | lock: {}",
+                        schedule.index_name(),
+                        current_index,
+                        doc_count,
+                        watermark,
+                        lock_state
+                    )?;
+                }
+            }
+            CliCommand::ValidateConfig => {
+                let mut problem_cnt: usize = 0;
+
+                for (idx, schedule) in index_schedules.index().iter().enumerate() {
+                    for problem in schedule.validate() {
+                        problem_cnt += 1;
+                        writeln!(
+                            stdout,
+                            "[{}] {} ({}): {}",
+                            idx + 1,
+                            schedule.index_name(),
+                            schedule.function_name(),
+                            problem
+                        )?;
+                    }
+                }
+
+                /* 스케쥴러에서만 쓰이는 전역 설정도 함께 검증해준다. */
+                for problem in index_schedules.validate_global() {
+                    problem_cnt += 1;
+                    writeln!(stdout, "[-] {}", problem)?;
+                }
+
+                if problem_cnt > 0 {
+                    return Err(anyhow!(
+                        "[Error][cli_command_task()] The index list has {} problem(s).",
+                        problem_cnt
+                    ));
+                }
+
+                writeln!(stdout, "Configuration is valid.")?;
+            }
+            CliCommand::Rollback { index, to } => match to {
+                Some(target_index_name) => {
+                    if !confirm_user_input(
+                        &format!("Point '{}' to '{}'? [y/N]: ", index, target_index_name),
+                        assume_yes,
+                    )? {
+                        writeln!(stdout, "Rollback canceled.")?;
+                        return Ok(());
+                    }
+
+                    self.es_query_service
+                        .rollback_index_alias(&index, &target_index_name)
+                        .await?;
+
+                    info!(
+                        "[cli_command_task()] '{}' was rolled back to '{}'",
+                        index, target_index_name
+                    );
+                    writeln!(stdout, "Rollback operation completed.")?;
+                }
+                None => {
+                    let generations: Vec<IndexGeneration> =
+                        self.es_query_service.get_index_generations(&index).await?;
+
+                    print_index_generations(&mut stdout, &index, &generations)?;
+                }
+            },
+            CliCommand::Preview { index, count } => {
+                let schedules: Vec<&IndexSchedules> = index_schedules
+                    .index()
+                    .iter()
+                    .filter(|schedule| {
+                        index
+                            .as_ref()
+                            .is_none_or(|index| index == schedule.index_name())
+                    })
+                    .collect();
+
+                if schedules.is_empty() {
+                    return Err(anyhow!(
+                        "[Error][cli_command_task()] No '{}' index in the index list.",
+                        index.unwrap_or_default()
+                    ));
+                }
+
+                for schedule in schedules {
+                    let cron_schedule: Schedule = schedule.cron_schedule()?;
+                    let timezone: Tz = schedule.cron_timezone()?;
+
+                    writeln!(
+                        stdout,
+                        "\n{} ({}) | cron: {} | timezone: {}",
+                        schedule.index_name(),
+                        schedule.function_name(),
+                        schedule.time(),
+                        timezone
+                    )?;
+
+                    for fire_time in cron_schedule.upcoming(timezone).take(count) {
+                        writeln!(
+                            stdout,
+                            "  {}",
+                            fire_time.format("%Y-%m-%d %H:%M:%S %Z (%:z)")
+                        )?;
+                    }
+                }
+            }
+            CliCommand::History { index, limit } => {
+                let mut index_aliases: Vec<&String> = index_schedules
+                    .index()
+                    .iter()
+                    .map(|schedule| schedule.index_name())
+                    .filter(|index_alias| index.as_ref().is_none_or(|index| index == *index_alias))
+                    .collect();
+                index_aliases.sort();
+                index_aliases.dedup();
+
+                if index_aliases.is_empty() {
+                    return Err(anyhow!(
+                        "[Error][cli_command_task()] No '{}' index in the index list.",
+                        index.unwrap_or_default()
+                    ));
+                }
+
+                for index_alias in index_aliases {
+                    let run_histories: Vec<elastic_index_run_history_tbl::Model> = self
+                        .query_service
+                        .get_run_histories(index_alias, limit)
+                        .await?;
+
+                    print_run_histories(&mut stdout, index_alias, &run_histories)?;
+                }
+            }
+            CliCommand::Schedule => {
+                return Err(anyhow!(
+                    "[Error][cli_command_task()] 'schedule' is not a one-shot command."
+                ));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[doc = "인덱스의 실행 이력을 출력해주는 함수"]
+/// # Arguments
+/// * `stdout` - 표준출력
+/// * `index_alias` - index alias name
+/// * `run_histories` - 최신순으로 정렬된 실행 이력
+///
+/// # Returns
+/// * Result<(), anyhow::Error>
+fn print_run_histories(
+    stdout: &mut io::Stdout,
+    index_alias: &str,
+    run_histories: &[elastic_index_run_history_tbl::Model],
+) -> Result<(), anyhow::Error> {
+    writeln!(stdout, "\nRecent runs of '{}'", index_alias)?;
+
+    if run_histories.is_empty() {
+        writeln!(stdout, "There is no run history.")?;
+        return Ok(());
+    }
+
+    for run_history in run_histories {
+        writeln!(
+            stdout,
+            "[{}] {} ~ {} | {} ({}) | {} #{} | {} | read: {}, written: {}, deleted: {}, failed: {} | index: {}",
+            run_history.run_id,
+            get_str_from_naive_datetime(run_history.start_dt),
+            run_history
+                .end_dt
+                .map(get_str_from_naive_datetime)
+                .unwrap_or_else(|| "-".to_string()),
+            run_history.function_name,
+            run_history.indexing_type,
+            run_history.run_trigger,
+            run_history.attempt,
+            run_history.run_status,
+            run_history.rows_read,
+            run_history.documents_written,
+            run_history.documents_deleted,
+            run_history.documents_failed,
+            run_history.generated_index_name.as_deref().unwrap_or("-")
+        )?;
+
+        if let Some(error_message) = &run_history.error_message {
+            writeln!(
+                stdout,
+                "    error ({}): {}",
+                run_history.error_class.as_deref().unwrap_or("-"),
+                error_message
+            )?;
+        }
+    }
+
+    Ok(())
+}
+
+#[doc = "스케쥴러 루프의 상태로 관리 API 에서 보여줄 스케쥴 상태를 만들어주는 함수"]
+/// # Arguments
+/// * `index_name` - 인덱스(alias) 이름
+/// * `entries` - 스케쥴 상태들
+/// * `running` - 실행중인 작업
+/// * `pending` - 대기중인 작업들
+///
+/// # Returns
+/// * Vec<ScheduleStatus>
+fn build_schedule_statuses(
+    index_name: &str,
+    entries: &[ScheduleEntry],
+    running: &Option<RunningTask<'_>>,
+    pending: &VecDeque<(IndexSchedules, RunTrigger)>,
+) -> Vec<ScheduleStatus> {
+    entries
+        .iter()
+        .map(|entry| {
+            let function_name: &String = entry.index_schedule.function_name();
+
+            ScheduleStatus {
+                index_name: index_name.to_string(),
+                function_name: function_name.clone(),
+                indexing_type: entry.index_schedule.indexing_type().clone(),
+                cron: entry.index_schedule.time().clone(),
+                timezone: entry.timezone.to_string(),
+                paused: entry.paused,
+                running: running.as_ref().is_some_and(|(running_schedule, _, _, _)| {
+                    running_schedule.function_name() == function_name
+                }),
+                queued: pending
+                    .iter()
+                    .any(|(queued, _)| queued.function_name() == function_name),
+                next_fire: entry
+                    .next_fire()
+                    .map(|next_fire| next_fire.with_timezone(&entry.timezone).to_rfc3339()),
+                last_result: entry.last_result.clone(),
+            }
+        })
+        .collect()
+}
+
+#[doc = "실행중인 작업에 취소 신호를 보내주는 함수"]
+/// # Arguments
+/// * `running` - 실행중인 작업
+/// * `reason` - 취소 사유
+///
+/// # Returns
+/// * Option<String> - 취소 신호를 보낸 색인 함수 이름. 실행중인 작업이 없으면 None
+fn cancel_running_task(running: &Option<RunningTask<'_>>, reason: String) -> Option<String> {
+    let (running_schedule, _, cancel_tx, _) = running.as_ref()?;
+
+    /* 이미 취소 신호를 받은 작업은 처음 받은 사유를 유지한다. */
+    cancel_tx.send_if_modified(|cancel_reason| {
+        if cancel_reason.is_some() {
+            return false;
+        }
+        *cancel_reason = Some(reason);
+        true
+    });
+
+    Some(running_schedule.function_name().clone())
+}
+
+#[doc = "색인 작업의 취소 신호를 기다려주는 함수 -> 취소 신호를 보내는 쪽이 없어지면 취소되지 않는다."]
+/// # Arguments
+/// * `cancel_signal` - 색인 작업의 취소 신호
+///
+/// # Returns
+/// * String - 취소 사유
+async fn wait_for_cancel(mut cancel_signal: CancelSignal) -> String {
+    loop {
+        if let Some(reason) = cancel_signal.borrow_and_update().clone() {
+            return reason;
+        }
+
+        if cancel_signal.changed().await.is_err() {
+            return std::future::pending().await;
+        }
+    }
+}
+
+#[doc = "취소되지 않는 색인 작업의 취소 신호를 만들어주는 함수 -> CLI 에서 실행하는 작업에 사용한다."]
+/// # Returns
+/// * CancelSignal
+fn create_uncancelable_signal() -> CancelSignal {
+    watch::channel::<Option<String>>(None).1
+}
+
+#[doc = "스케쥴을 멈추거나 다시 시작해주는 함수"]
+/// # Arguments
+/// * `index_name` - 인덱스(alias) 이름
+/// * `entries` - 스케쥴 상태들
+/// * `function_name` - 대상 색인 함수 이름 (None 이면 인덱스의 모든 스케쥴)
+/// * `paused` - 멈출지 여부
+///
+/// # Returns
+/// * Result<String, anyhow::Error> - 처리 결과 메시지
+fn set_schedules_paused(
+    index_name: &str,
+    entries: &mut [ScheduleEntry],
+    function_name: Option<String>,
+    paused: bool,
+) -> Result<String, anyhow::Error> {
+    let mut changed_functions: Vec<String> = Vec::new();
+
+    for entry in entries.iter_mut().filter(|entry| {
+        function_name
+            .as_ref()
+            .is_none_or(|function_name| entry.index_schedule.function_name() == function_name)
+    }) {
+        /* 다시 시작하는 경우 멈춰있던 동안의 실행 시각은 건너뛴다. */
+        if entry.paused && !paused {
+            entry.last_fire = Utc::now();
+        }
+
+        entry.paused = paused;
+        changed_functions.push(entry.index_schedule.function_name().clone());
+    }
+
+    if changed_functions.is_empty() {
+        return Err(anyhow!(
+            "[Error][set_schedules_paused()] '{}' has no '{}' schedule.",
+            index_name,
+            function_name.unwrap_or_default()
+        ));
+    }
+
+    Ok(format!(
+        "'{}' ({}) {}",
+        index_name,
+        changed_functions.join(", "),
+        if paused { "paused" } else { "resumed" }
+    ))
+}
+
+#[doc = "인덱스 lock 의 소유자 식별자를 만들어주는 함수 -> {hostname}-{pid}-{random}"]
+/// # Returns
+/// * String
+fn create_lock_owner_id() -> String {
+    let hostname: String = env::var("HOSTNAME").unwrap_or_else(|_| "unknown-host".to_string());
+
+    format!(
+        "{}-{}-{:08x}",
+        hostname,
+        std::process::id(),
+        rand::random::<u32>()
+    )
+}
+
+#[doc = "인덱스 세대 목록을 출력해주는 함수"]
+/// # Arguments
+/// * `stdout` - 표준출력
+/// * `index_alias` - index alias name
+/// * `generations` - 최신순으로 정렬된 인덱스 세대 목록
+///
+/// # Returns
+/// * Result<(), anyhow::Error>
+fn print_index_generations(
+    stdout: &mut io::Stdout,
+    index_alias: &str,
+    generations: &[IndexGeneration],
+) -> Result<(), anyhow::Error> {
+    writeln!(stdout, "\nGenerations of '{}'", index_alias)?;
+
+    for (idx, generation) in generations.iter().enumerate() {
+        writeln!(
+            stdout,
+            "[{}] {} | docs: {} | created: {}{}",
+            idx + 1,
+            generation.index_name(),
+            generation.doc_count(),
+            generation.creation_date(),
+            if *generation.is_alias_target() {
+                " (current)"
+            } else {
+                ""
+            }
+        )?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::services::call_log::*;
+    use crate::services::in_memory_es_query_service::*;
+    use crate::services::in_memory_notification_service::*;
+    use crate::services::in_memory_query_service::*;
+
+    type TestController =
+        MainController<InMemoryQueryService, InMemoryEsQueryService, InMemoryNotificationService>;
+
+    const INDEX_NAME: &str = "store";
+
+    fn index_schedule(function_name: &str, indexing_type: &str) -> IndexSchedules {
+        toml::from_str(&format!(
+            r#"
+            index_name = "{}"
+            time = "0 0 * * * *"
+            indexing_type = "{}"
+            setting_path = "settings/store.json"
+            function_name = "{}"
+            sql_batch_size = 2
+            es_batch_size = 2
+            "#,
+            INDEX_NAME, indexing_type, function_name
+        ))
+        .expect("invalid test index schedule")
+    }
+
+    fn watermark() -> NaiveDateTime {
+        get_naive_datetime_from_str("2025-01-01T00:00:00Z", "%Y-%m-%dT%H:%M:%SZ")
+            .expect("invalid test watermark")
+    }
+
+    fn controller(
+        call_log: &CallLog,
+        query_service: InMemoryQueryService,
+        es_query_service: InMemoryEsQueryService,
+    ) -> TestController {
+        MainController::new(
+            query_service,
+            es_query_service,
+            InMemoryNotificationService::new(call_log.clone()),
+        )
+    }
+
+    fn indexed_seqs(documents: &[Value]) -> Vec<i64> {
+        documents
+            .iter()
+            .filter_map(|document| document["seq"].as_i64())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn store_static_index_indexes_typed_stores_and_swaps_alias() {
+        let call_log: CallLog = CallLog::default();
+        let query_service: InMemoryQueryService = InMemoryQueryService::new(call_log.clone())
+            .with_store(1, "store-1")
+            .with_store(2, "store-2")
+            .with_store(3, "store-3")
+            .with_store_type(1, 10, 101)
+            .with_store_type(2, 10, 102)
+            .with_store_type(3, 20, 201);
+        let es_query_service: InMemoryEsQueryService =
+            InMemoryEsQueryService::new(call_log.clone()).with_alias(INDEX_NAME, "store-old");
+        let controller: TestController = controller(&call_log, query_service, es_query_service);
+
+        let mut run_report: RunReport = RunReport::default();
+        controller
+            .store_static_index(
+                index_schedule("store_static_index", "static"),
+                &mut run_report,
+            )
+            .await
+            .expect("static indexing failed");
+
+        let new_index_name: String = "store-20250101000000".to_string();
+        assert_eq!(
+            controller.es_query_service.alias_target(INDEX_NAME),
+            Some(new_index_name.clone())
+        );
+        assert_eq!(run_report.generated_index_name, Some(new_index_name));
+        assert_eq!(run_report.rows_read, 3);
+        assert_eq!(run_report.documents_written, 3);
+
+        let documents: Vec<Value> = controller.es_query_service.documents(INDEX_NAME);
+        assert_eq!(indexed_seqs(&documents), vec![1, 2, 3]);
+        assert_eq!(documents[2]["major_type"], json!([20]));
+        assert_eq!(documents[2]["sub_type_name"], json!(["sub-201"]));
+
+        /* sql_batch_size = 2 -> 두 페이지를 읽은 뒤 빈 페이지에서 멈춘다. */
+        assert_eq!(call_log.count("get_all_store_table"), 3);
+
+        let create_pos: Option<usize> = call_log.position("create_static_index");
+        let post_pos: Option<usize> = call_log.position("post_indexing_data_by_bulk");
+        let swap_pos: Option<usize> = call_log.position("swap_static_index_alias");
+        let update_pos: Option<usize> =
+            call_log.position("update_recent_date_to_elastic_index_info");
+
+        assert!(create_pos < post_pos);
+        assert!(post_pos < swap_pos);
+        assert!(swap_pos < update_pos);
+        assert!(controller.query_service.recent_date(INDEX_NAME).is_some());
+    }
+
+    #[tokio::test]
+    async fn store_static_index_keeps_alias_and_watermark_when_swap_fails() {
+        let call_log: CallLog = CallLog::default();
+        let query_service: InMemoryQueryService = InMemoryQueryService::new(call_log.clone())
+            .with_store(1, "store-1")
+            .with_store_type(1, 10, 101)
+            .with_recent_date(INDEX_NAME, watermark());
+        let es_query_service: InMemoryEsQueryService =
+            InMemoryEsQueryService::new(call_log.clone())
+                .with_alias(INDEX_NAME, "store-old")
+                .with_failing_alias_swap();
+        let controller: TestController = controller(&call_log, query_service, es_query_service);
+
+        let mut run_report: RunReport = RunReport::default();
+        let error: anyhow::Error = controller
+            .store_static_index(
+                index_schedule("store_static_index", "static"),
+                &mut run_report,
+            )
+            .await
+            .expect_err("the alias swap should have failed");
+
+        assert!(matches!(
+            find_indexing_error(&error),
+            Some(IndexingError::Validation { .. })
+        ));
+        assert_eq!(RunErrorClass::classify(&error), RunErrorClass::Validation);
+
+        assert_eq!(
+            controller.es_query_service.alias_target(INDEX_NAME),
+            Some("store-old".to_string())
+        );
+        assert_eq!(
+            controller.query_service.recent_date(INDEX_NAME),
+            Some(watermark())
+        );
+        assert_eq!(
+            call_log.count("update_recent_date_to_elastic_index_info"),
+            0
+        );
+
+        /* 검증에 실패한 인덱스는 확인할 수 있도록 남겨둔다. */
+        assert_eq!(
+            run_report.generated_index_name,
+            Some("store-20250101000000".to_string())
+        );
+        assert_eq!(
+            controller
+                .es_query_service
+                .documents("store-20250101000000")
+                .len(),
+            1
+        );
+    }
+
+    #[tokio::test]
+    async fn store_static_index_fails_before_swap_when_store_type_is_missing() {
+        let call_log: CallLog = CallLog::default();
+        let query_service: InMemoryQueryService = InMemoryQueryService::new(call_log.clone())
+            .with_store(1, "store-1")
+            .with_store(2, "store-2")
+            .with_store_type(1, 10, 101);
+        let controller: TestController = controller(
+            &call_log,
+            query_service,
+            InMemoryEsQueryService::new(call_log.clone()).with_alias(INDEX_NAME, "store-old"),
+        );
+
+        let mut run_report: RunReport = RunReport::default();
+        let error: anyhow::Error = controller
+            .store_static_index(
+                index_schedule("store_static_index", "static"),
+                &mut run_report,
+            )
+            .await
+            .expect_err("indexing should fail for a store without a store type");
+
+        assert!(format!("{:#}", error).contains("seq: 2"));
+        assert_eq!(call_log.count("swap_static_index_alias"), 0);
+        assert_eq!(
+            call_log.count("update_recent_date_to_elastic_index_info"),
+            0
+        );
+        assert_eq!(
+            controller.es_query_service.alias_target(INDEX_NAME),
+            Some("store-old".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn main_task_cancel_releases_lock_and_deletes_generated_index() {
+        let call_log: CallLog = CallLog::default();
+        let query_service: InMemoryQueryService = InMemoryQueryService::new(call_log.clone())
+            .with_store(1, "store-1")
+            .with_store_type(1, 10, 101);
+        let controller: TestController = controller(
+            &call_log,
+            query_service,
+            InMemoryEsQueryService::new(call_log.clone())
+                .with_alias(INDEX_NAME, "store-old")
+                .with_stalled_alias_swap(),
+        );
+
+        let (cancel_tx, cancel_rx) = watch::channel::<Option<String>>(None);
+
+        /* alias 교체 단계에서 멈춰있는 작업에 취소 신호를 보낸다. */
+        let cancel = async {
+            while call_log.count("swap_static_index_alias") == 0 {
+                tokio::task::yield_now().await;
+            }
+            cancel_tx
+                .send(Some("canceled by test".to_string()))
+                .expect("the run should still be waiting for the cancel signal");
+        };
+
+        let (run_result, _) = tokio::join!(
+            controller.main_task(
+                index_schedule("store_static_index", "static"),
+                RunTrigger::Api,
+                1,
+                cancel_rx,
+            ),
+            cancel
+        );
+
+        let error: anyhow::Error = run_result.expect_err("the run should be canceled");
+        assert!(is_canceled_error(&error));
+
+        let run_histories = controller
+            .query_service
+            .get_run_histories(INDEX_NAME, 10)
+            .await
+            .expect("run histories should be readable");
+        assert_eq!(run_histories.len(), 1);
+        assert_eq!(run_histories[0].run_status, RUN_STATUS_CANCELED);
+        assert_eq!(run_histories[0].error_class, None);
+
+        assert!(controller
+            .query_service
+            .get_index_lock(INDEX_NAME)
+            .await
+            .expect("index lock should be readable")
+            .is_none());
+
+        let generations: Vec<IndexGeneration> = controller
+            .es_query_service
+            .get_index_generations(INDEX_NAME)
+            .await
+            .expect("index generations should be readable");
+        /* 취소된 작업이 만든 세대는 지워지고 기존 세대만 남는다. */
+        assert_eq!(
+            generations
+                .iter()
+                .map(|generation| generation.index_name.as_str())
+                .collect::<Vec<&str>>(),
+            vec!["store-old"]
+        );
+        assert_eq!(call_log.count("delete_index_generation"), 1);
+        assert_eq!(
+            controller.es_query_service.alias_target(INDEX_NAME),
+            Some("store-old".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn cli_run_failure_sends_run_failure_alert() {
+        let call_log: CallLog = CallLog::default();
+        let query_service: InMemoryQueryService = InMemoryQueryService::new(call_log.clone())
+            .with_store(1, "store-1")
+            .with_store(2, "store-2")
+            .with_store_type(1, 10, 101);
+        let controller: TestController = controller(
+            &call_log,
+            query_service,
+            InMemoryEsQueryService::new(call_log.clone()).with_alias(INDEX_NAME, "store-old"),
+        );
+
+        let index_schedules: IndexSchedulesConfig = IndexSchedulesConfig {
+            timezone: None,
+            notification: NotificationConfig::default(),
+            index: vec![index_schedule("store_static_index", "static")],
+        };
+
+        controller
+            .cli_command_task(
+                CliCommand::Run {
+                    index: INDEX_NAME.to_string(),
+                    indexing_type: CliIndexingType::Static,
+                },
+                index_schedules,
+                true,
+            )
+            .await
+            .expect_err("the run should fail for a store without a store type");
+
+        assert_eq!(call_log.count("notify_run_failure"), 1);
+        assert_eq!(call_log.count("notify_validation_blocked"), 0);
+    }
+
+    #[tokio::test]
+    async fn store_dynamic_index_keeps_watermark_for_empty_change_set() {
+        let call_log: CallLog = CallLog::default();
+        let query_service: InMemoryQueryService = InMemoryQueryService::new(call_log.clone())
+            .with_store(1, "store-1")
+            .with_store_type(1, 10, 101)
+            .with_recent_date(INDEX_NAME, watermark());
+        let es_query_service: InMemoryEsQueryService =
+            InMemoryEsQueryService::new(call_log.clone())
+                .with_alias(INDEX_NAME, "store-old")
+                .with_document(INDEX_NAME, "1", json!({ "seq": 1 }));
+        let controller: TestController = controller(&call_log, query_service, es_query_service);
+
+        let mut run_report: RunReport = RunReport::default();
+        controller
+            .store_dynamic_index(
+                index_schedule("store_dynamic_index", "dynamic"),
+                &mut run_report,
+            )
+            .await
+            .expect("dynamic indexing failed");
+
+        assert_eq!(call_log.count("get_specific_store_table"), 1);
+        assert_eq!(call_log.count("post_indexing_data_by_bulk"), 0);
+        assert_eq!(call_log.count("delete_index_by_field_values"), 0);
+        assert_eq!(
+            call_log.count("update_recent_date_to_elastic_index_info"),
+            0
+        );
+        assert_eq!(
+            controller.query_service.recent_date(INDEX_NAME),
+            Some(watermark())
+        );
+        assert_eq!(run_report.documents_written, 0);
+        assert_eq!(run_report.documents_deleted, 0);
+    }
+
+    #[tokio::test]
+    async fn store_dynamic_index_removes_inactive_stores_before_watermark_update() {
+        let call_log: CallLog = CallLog::default();
+        let query_service: InMemoryQueryService = InMemoryQueryService::new(call_log.clone())
+            .with_store(1, "store-1")
+            .with_store(2, "store-2")
+            .with_store_type(1, 10, 101)
+            .with_store_type(2, 10, 102)
+            .mark_removed(2)
+            .with_recent_date(INDEX_NAME, watermark());
+        let es_query_service: InMemoryEsQueryService =
+            InMemoryEsQueryService::new(call_log.clone())
+                .with_alias(INDEX_NAME, "store-old")
+                .with_document(INDEX_NAME, "1", json!({ "seq": 1 }))
+                .with_document(INDEX_NAME, "2", json!({ "seq": 2 }));
+        let controller: TestController = controller(&call_log, query_service, es_query_service);
+
+        let mut run_report: RunReport = RunReport::default();
+        controller
+            .store_dynamic_index(
+                index_schedule("store_dynamic_index", "dynamic"),
+                &mut run_report,
+            )
+            .await
+            .expect("dynamic indexing failed");
+
+        assert_eq!(
+            indexed_seqs(&controller.es_query_service.documents(INDEX_NAME)),
+            vec![1]
+        );
+        assert_eq!(run_report.documents_deleted, 1);
+        assert_eq!(call_log.count("post_indexing_data_by_bulk"), 0);
+
+        let delete_pos: Option<usize> = call_log.position("delete_index_by_field_values");
+        let update_pos: Option<usize> =
+            call_log.position("update_recent_date_to_elastic_index_info");
+
+        assert!(delete_pos.is_some());
+        assert!(delete_pos < update_pos);
+        assert!(controller.query_service.recent_date(INDEX_NAME) > Some(watermark()));
+    }
+
+    #[tokio::test]
+    async fn store_dynamic_index_upserts_changed_stores_before_watermark_update() {
+        let call_log: CallLog = CallLog::default();
+        let query_service: InMemoryQueryService = InMemoryQueryService::new(call_log.clone())
+            .with_store(1, "store-1")
+            .with_store(2, "store-2")
+            .with_store(3, "store-3")
+            .with_store_type(1, 10, 101)
+            .with_store_type(2, 10, 102)
+            .with_store_type(3, 20, 201)
+            .mark_changed(1)
+            .mark_changed(3)
+            .with_recent_date(INDEX_NAME, watermark());
+        let es_query_service: InMemoryEsQueryService =
+            InMemoryEsQueryService::new(call_log.clone())
+                .with_alias(INDEX_NAME, "store-old")
+                .with_document(INDEX_NAME, "1", json!({ "seq": 1, "name": "old-store-1" }))
+                .with_document(INDEX_NAME, "2", json!({ "seq": 2, "name": "store-2" }));
+        let controller: TestController = controller(&call_log, query_service, es_query_service);
+
+        let mut run_report: RunReport = RunReport::default();
+        controller
+            .store_dynamic_index(
+                index_schedule("store_dynamic_index", "dynamic"),
+                &mut run_report,
+            )
+            .await
+            .expect("dynamic indexing failed");
+
+        let documents: Vec<Value> = controller.es_query_service.documents(INDEX_NAME);
+        assert_eq!(indexed_seqs(&documents), vec![1, 2, 3]);
+        assert_eq!(documents[0]["name"], json!("store-1"));
+        assert_eq!(documents[2]["major_type"], json!([20]));
+
+        assert_eq!(run_report.documents_written, 2);
+        assert_eq!(run_report.documents_deleted, 0);
+        assert_eq!(call_log.count("delete_index_by_field_values"), 0);
+
+        let post_pos: Option<usize> = call_log.position("post_indexing_data_by_bulk");
+        let removed_pos: Option<usize> = call_log.position("get_removed_store_seqs");
+        let update_pos: Option<usize> =
+            call_log.position("update_recent_date_to_elastic_index_info");
+
+        assert!(post_pos.is_some());
+        assert!(post_pos < removed_pos);
+        assert!(removed_pos < update_pos);
+        assert_eq!(
+            call_log.calls().last().map(String::as_str),
+            Some("update_recent_date_to_elastic_index_info")
+        );
+    }
+
+    #[tokio::test]
+    async fn handling_store_type_fills_major_and_sub_types() {
+        let call_log: CallLog = CallLog::default();
+        let query_service: InMemoryQueryService = InMemoryQueryService::new(call_log.clone())
+            .with_store(1, "store-1")
+            .with_store(2, "store-2")
+            .with_store_type(1, 10, 101)
+            .with_store_type(1, 10, 102)
+            .with_store_type(2, 20, 201);
+        let controller: TestController = controller(
+            &call_log,
+            query_service,
+            InMemoryEsQueryService::new(call_log.clone()),
+        );
+
+        let mut stores: Vec<DistinctStoreResult> = controller
+            .query_service
+            .get_all_store_table(
+                &index_schedule("store_static_index", "static"),
+                watermark(),
+                None,
+            )
+            .await
+            .expect("failed to read stores");
+
+        controller
+            .handling_store_type(Some(vec![1, 2]), &mut stores)
+            .await
+            .expect("failed to fill store types");
+
+        assert_eq!(stores[0].major_type, vec![10]);
+        assert_eq!(stores[0].sub_type, vec![101, 102]);
+        assert_eq!(stores[0].major_type_name, vec!["major-10".to_string()]);
+        assert_eq!(
+            stores[0].sub_type_name,
+            vec!["sub-101".to_string(), "sub-102".to_string()]
+        );
+        assert_eq!(stores[1].major_type, vec![20]);
+        assert_eq!(stores[1].sub_type, vec![201]);
+    }
+
+    #[tokio::test]
+    async fn handling_store_type_fails_for_seq_without_store_type() {
+        let call_log: CallLog = CallLog::default();
+        let query_service: InMemoryQueryService = InMemoryQueryService::new(call_log.clone())
+            .with_store(1, "store-1")
+            .with_store(2, "store-2")
+            .with_store_type(1, 10, 101);
+        let controller: TestController = controller(
+            &call_log,
+            query_service,
+            InMemoryEsQueryService::new(call_log.clone()),
+        );
+
+        let mut stores: Vec<DistinctStoreResult> = controller
+            .query_service
+            .get_all_store_table(
+                &index_schedule("store_static_index", "static"),
+                watermark(),
+                None,
+            )
+            .await
+            .expect("failed to read stores");
+
+        let error: anyhow::Error = controller
+            .handling_store_type(None, &mut stores)
+            .await
+            .expect_err("store 2 has no store type");
+
+        assert!(error
+            .to_string()
+            .contains("No 'seq' corresponding to 'store_type_major_map'. seq: 2"));
+    }
 }
@@ -0,0 +1,49 @@
+use crate::common::*;
+
+use crate::utils_module::metrics_utils::*;
+
+#[doc = "Prometheus 지표 endpoint(/metrics)를 열어주는 함수 -> 스케쥴 모드에서만 실행된다."]
+/// # Arguments
+/// * `listen_addr` - 바인딩할 주소 (예: 0.0.0.0:9898)
+///
+/// # Returns
+/// * Result<(), anyhow::Error>
+pub async fn serve_metrics(listen_addr: String) -> Result<(), anyhow::Error> {
+    let router: Router = Router::new().route("/metrics", get(get_metrics));
+
+    let listener: TcpListener = TcpListener::bind(&listen_addr).await.map_err(|e| {
+        anyhow!(
+            "[Error][serve_metrics()] Failed to bind '{}': {}",
+            listen_addr,
+            e
+        )
+    })?;
+
+    info!(
+        "[serve_metrics()] Prometheus metrics are served on http://{}/metrics",
+        listen_addr
+    );
+
+    axum::serve(listener, router).await?;
+
+    Ok(())
+}
+
+#[doc = "GET /metrics - 수집된 지표를 Prometheus text format 으로 응답해주는 함수"]
+async fn get_metrics() -> impl IntoResponse {
+    match get_indexing_metrics().gather_text() {
+        Ok(metrics_text) => (
+            StatusCode::OK,
+            [(CONTENT_TYPE, "text/plain; version=0.0.4")],
+            metrics_text,
+        ),
+        Err(e) => {
+            error!("[Error][get_metrics()] {:?}", e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                [(CONTENT_TYPE, "text/plain")],
+                format!("{:#}", e),
+            )
+        }
+    }
+}
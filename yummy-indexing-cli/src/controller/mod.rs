@@ -1 +1,3 @@
+pub mod admin_controller;
 pub mod main_controller;
+pub mod metrics_controller;
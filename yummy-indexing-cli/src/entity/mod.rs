@@ -3,6 +3,8 @@
 pub mod prelude;
 
 pub mod elastic_index_info_tbl;
+pub mod elastic_index_lock_tbl;
+pub mod elastic_index_run_history_tbl;
 pub mod migrations;
 pub mod recommend_tbl;
 pub mod store;
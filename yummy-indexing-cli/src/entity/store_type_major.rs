@@ -9,8 +9,8 @@ use crate::entity::store_type_sub;
 #[sea_orm(table_name = "store_type_major")]
 pub struct Model {
     #[sea_orm(primary_key, auto_increment = true)]
-    major_type: i32,
-    type_name: String,
+    pub major_type: i32,
+    pub type_name: String,
     pub reg_dt: DateTime,
     pub chg_dt: Option<DateTime>,
     pub reg_id: String,
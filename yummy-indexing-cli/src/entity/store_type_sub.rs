@@ -10,9 +10,9 @@ use super::store_type_sub;
 #[sea_orm(table_name = "store_type_sub")]
 pub struct Model {
     #[sea_orm(primary_key, auto_increment = true)]
-    sub_type: i32,
-    major_type: i32,
-    type_name: String,
+    pub sub_type: i32,
+    pub major_type: i32,
+    pub type_name: String,
     pub reg_dt: DateTime,
     pub chg_dt: Option<DateTime>,
     pub reg_id: String,
@@ -18,19 +18,26 @@ use utils_module::io_utils::*;
 use utils_module::logger_utils::*;
 
 mod repository;
+use repository::es_node_pool::*;
+use repository::telegram_repository::*;
 
 mod services;
 use services::es_query_service::*;
+use services::notification_service::*;
 use services::query_service::*;
 
 mod controller;
+use controller::admin_controller::*;
 use controller::main_controller::*;
+use controller::metrics_controller::*;
 
 mod configuration;
+use configuration::cli_config::*;
 use configuration::index_schedules_config::*;
 use configuration::system_config::*;
 
 mod models;
+use models::run_error::*;
 
 mod env_configuration;
 use env_configuration::env_config::*;
@@ -38,67 +45,108 @@ use env_configuration::env_config::*;
 mod entity;
 
 #[tokio::main]
-async fn main() {
+async fn main() -> ExitCode {
     set_global_logger();
     dotenv().ok();
 
+    /* 인자가 없으면 'COMPILE_TYPE' 에 따라 기존 방식(스케쥴/대화형 CLI)으로 동작한다. */
+    let cli_args: CliArgs = CliArgs::parse();
+
     info!("Yummy Indexing Batch Program Start");
 
-    let system_infos: Arc<SystemConfig> = get_system_config();
-    let compile_type: &str = system_infos.complie_type().as_str();
+    /* Elasticsearch 접속 설정이 잘못된 경우 바로 종료시켜준다. */
+    if let Err(e) = initialize_es_node_pool() {
+        error!("[Error][main()] {:?}", e);
+        eprintln!("[Error][main()] {}", e);
+        return get_exit_code(&e.into());
+    }
 
     let query_service: QueryServicePub = QueryServicePub::new();
     let es_query_service: EsQueryServicePub = EsQueryServicePub::new();
-    let controller_arc: Arc<MainController<QueryServicePub, EsQueryServicePub>> =
-        Arc::new(MainController::new(query_service, es_query_service));
+    let notification_service: NotificationServicePub = NotificationServicePub::new();
+    let controller_arc: Arc<
+        MainController<QueryServicePub, EsQueryServicePub, NotificationServicePub>,
+    > = Arc::new(MainController::new(
+        query_service,
+        es_query_service,
+        notification_service,
+    ));
 
     /* 모니터링 대상이 되는 색인될 인덱스 정보들 */
-    let index_schdules: IndexSchedulesConfig =
+    let mut index_schdules: IndexSchedulesConfig =
         match read_toml_from_file::<IndexSchedulesConfig>(&INDEX_LIST_PATH) {
             Ok(index_schdules) => index_schdules,
             Err(e) => {
-                error!("{:?}", e);
-                panic!("{:?}", e);
+                error!("[Error][main()] Failed to read the index list.: {:?}", e);
+                eprintln!("[Error][main()] Failed to read the index list.: {:#}", e);
+                return ExitCode::FAILURE;
             }
         };
 
-    if compile_type == "schedule" {
-        /*
-            [스케쥴 타입의 색인 프로그램]
-            각 인덱스 별로 모니터링을 비동기적으로 실시해준다.
-            스케쥴링 대기 작업 진행
-        */
-        for index in index_schdules.index {
-            let index_clone: IndexSchedules = index.clone();
+    index_schdules.inherit_global_settings();
+    initialize_telegram_client(index_schdules.notification());
 
-            let controller_arc_clone: Arc<MainController<QueryServicePub, EsQueryServicePub>> =
-                Arc::clone(&controller_arc);
+    /* Elasticsearch 노드 health check -> 장애 노드를 격리하고 복구되면 다시 사용한다. (CLI 실행 중에도 필요) */
+    tokio::spawn(async move {
+        run_es_node_health_check().await;
+    });
 
-            tokio::spawn(async move {
-                if let Err(e) = controller_arc_clone.main_schedule_task(index_clone).await {
-                    error!("[Error][main_schedule_task] {:?}", e);
-                }
-            });
-        }
+    match cli_args.command {
+        Some(CliCommand::Schedule) => {
+            if !check_schedules(&index_schdules) {
+                return ExitCode::FAILURE;
+            }
 
-        /* 모두 서브테스크로 실행되므로 아래와 같이 메인 태스크를 계속 유지시켜줘야 한다. */
-        tokio::select! {
-            _ = signal::ctrl_c() => {
-                info!("Received Ctrl+C, shutting down...");
+            run_schedule_daemon(controller_arc, index_schdules).await
+        }
+        Some(command) => {
+            /* [서브커맨드를 받아서 처리하는 프로그램] -> 성공 여부를 exit code 로 반환 */
+            match controller_arc
+                .cli_command_task(command, index_schdules, cli_args.yes)
+                .await
+            {
+                Ok(_) => ExitCode::SUCCESS,
+                Err(e) => {
+                    error!("[Error][main()] {:?}", e);
+                    eprintln!("[Error][main()] {:#}", e);
+                    /* 실패 원인에 따라 종료코드를 나눠 호출하는 쪽에서 재실행 여부를 판단할 수 있게 한다. */
+                    get_exit_code(&e)
+                }
             }
         }
-    } else if compile_type == "cli" {
-        /* [사용자 입력을 받아서 색인을 처리하는 프로그램] */
-        match controller_arc.cli_indexing_task(index_schdules).await {
-            Ok(_) => (),
-            Err(e) => {
-                error!("[Error][main()] {:?}", e);
-                panic!("[Error][main()] {:?}", e);
+        None => {
+            let system_infos: Arc<SystemConfig> = get_system_config();
+            let compile_type: &str = system_infos.complie_type().as_str();
+
+            if compile_type == "schedule" {
+                if !check_schedules(&index_schdules) {
+                    return ExitCode::FAILURE;
+                }
+
+                run_schedule_daemon(controller_arc, index_schdules).await
+            } else if compile_type == "cli" {
+                /* [사용자 입력을 받아서 색인을 처리하는 프로그램] */
+                match controller_arc.cli_indexing_task(index_schdules).await {
+                    Ok(_) => ExitCode::SUCCESS,
+                    Err(e) => {
+                        error!("[Error][main()] {:?}", e);
+                        panic!("[Error][main()] {:?}", e);
+                    }
+                }
+            } else if compile_type == "rollback" {
+                /* [정적색인 인덱스의 alias 를 이전 세대로 되돌리는 프로그램] */
+                match controller_arc.cli_rollback_task(index_schdules).await {
+                    Ok(_) => ExitCode::SUCCESS,
+                    Err(e) => {
+                        error!("[Error][main()] {:?}", e);
+                        panic!("[Error][main()] {:?}", e);
+                    }
+                }
+            } else {
+                error!("[Error][main()] The 'COMPILE_TYPE' information must be 'schedule', 'cli' or 'rollback'.");
+                panic!("[Error][main()] The 'COMPILE_TYPE' information must be 'schedule', 'cli' or 'rollback'.");
             }
         }
-    } else {
-        error!("[Error][main()] The 'COMPILE_TYPE' information must be 'schedule' or 'cli'.");
-        panic!("[Error][main()] The 'COMPILE_TYPE' information must be 'schedule' or 'cli'.");
     }
 
     /* test 코드 */
@@ -109,3 +157,126 @@ async fn main() {
     //     .await
     //     .unwrap();
 }
+
+#[doc = "스케쥴러를 띄우기 전에 cron 표현식과 timezone 을 검증해주는 함수 -> 잘못된 설정이 있으면 스케쥴러가 뜨기 전에 종료시켜준다."]
+/// # Arguments
+/// * `index_schdules` - 색인될 인덱스 정보들
+///
+/// # Returns
+/// * bool - 문제가 없으면 true
+fn check_schedules(index_schdules: &IndexSchedulesConfig) -> bool {
+    let schedule_problems: Vec<String> = index_schdules.validate_schedules();
+
+    for problem in &schedule_problems {
+        error!("[Error][check_schedules()] Invalid schedule.: {}", problem);
+        eprintln!("[Error][check_schedules()] Invalid schedule.: {}", problem);
+    }
+
+    schedule_problems.is_empty()
+}
+
+#[doc = "스케쥴 타입의 색인 프로그램을 실행해주는 함수"]
+/// # Arguments
+/// * `controller_arc` - 메인 컨트롤러
+/// * `index_schdules` - 인덱스 스케쥴 객체들
+///
+/// # Returns
+/// * ExitCode
+async fn run_schedule_daemon(
+    controller_arc: Arc<MainController<QueryServicePub, EsQueryServicePub, NotificationServicePub>>,
+    index_schdules: IndexSchedulesConfig,
+) -> ExitCode {
+    /*
+        [스케쥴 타입의 색인 프로그램]
+        각 인덱스 별로 모니터링을 비동기적으로 실시해준다.
+        스케쥴링 대기 작업 진행
+    */
+    /* 같은 인덱스(alias)의 스케쥴들은 하나의 스케쥴러가 관리하여 동시에 실행되지 않도록 해준다. */
+    let mut schedules_by_index: Vec<(String, Vec<IndexSchedules>)> = Vec::new();
+
+    /* 일일 요약은 check_schedules() 에서 검증되었으므로 설정이 있는 경우에만 실행된다. */
+    if let (Ok(Some(summary_schedule)), Ok(timezone)) = (
+        index_schdules.daily_summary_schedule(),
+        index_schdules.global_timezone(),
+    ) {
+        let index_aliases: Vec<String> = index_schdules
+            .index()
+            .iter()
+            .map(|index| index.index_name().clone())
+            .collect::<BTreeSet<String>>()
+            .into_iter()
+            .collect();
+
+        let controller_arc_clone: Arc<
+            MainController<QueryServicePub, EsQueryServicePub, NotificationServicePub>,
+        > = Arc::clone(&controller_arc);
+
+        tokio::spawn(async move {
+            controller_arc_clone
+                .daily_summary_task(index_aliases, summary_schedule, timezone)
+                .await;
+        });
+    }
+
+    /* Prometheus 지표 endpoint -> 'METRICS_LISTEN_ADDR' 가 설정된 경우에만 열어준다. */
+    match env::var("METRICS_LISTEN_ADDR") {
+        Ok(listen_addr) if !listen_addr.trim().is_empty() => {
+            tokio::spawn(async move {
+                if let Err(e) = serve_metrics(listen_addr).await {
+                    error!("[Error][serve_metrics()] {:?}", e);
+                }
+            });
+        }
+        _ => info!("'METRICS_LISTEN_ADDR' is not set. The metrics endpoint is disabled."),
+    }
+
+    /* 관리 API -> 'ADMIN_LISTEN_ADDR' 가 설정된 경우에만 열어준다. (loopback 주소 권장) */
+    match env::var("ADMIN_LISTEN_ADDR") {
+        Ok(listen_addr) if !listen_addr.trim().is_empty() => {
+            let controller_arc_clone: Arc<
+                MainController<QueryServicePub, EsQueryServicePub, NotificationServicePub>,
+            > = Arc::clone(&controller_arc);
+
+            tokio::spawn(async move {
+                if let Err(e) = serve_admin_api(listen_addr, controller_arc_clone).await {
+                    error!("[Error][serve_admin_api()] {:?}", e);
+                }
+            });
+        }
+        _ => info!("'ADMIN_LISTEN_ADDR' is not set. The admin API is disabled."),
+    }
+
+    for index in index_schdules.index {
+        match schedules_by_index
+            .iter_mut()
+            .find(|(index_name, _)| *index_name == index.index_name)
+        {
+            Some((_, schedules)) => schedules.push(index),
+            None => schedules_by_index.push((index.index_name.clone(), vec![index])),
+        }
+    }
+
+    for (index_name, schedules) in schedules_by_index {
+        let controller_arc_clone: Arc<
+            MainController<QueryServicePub, EsQueryServicePub, NotificationServicePub>,
+        > = Arc::clone(&controller_arc);
+
+        tokio::spawn(async move {
+            if let Err(e) = controller_arc_clone
+                .main_schedule_task(&index_name, schedules)
+                .await
+            {
+                error!("[Error][main_schedule_task] {:?}", e);
+            }
+        });
+    }
+
+    /* 모두 서브테스크로 실행되므로 아래와 같이 메인 태스크를 계속 유지시켜줘야 한다. */
+    tokio::select! {
+        _ = signal::ctrl_c() => {
+            info!("Received Ctrl+C, shutting down...");
+        }
+    }
+
+    ExitCode::SUCCESS
+}
@@ -0,0 +1,29 @@
+use crate::common::*;
+
+use crate::models::es_document::*;
+
+#[doc = "자동완성 키워드의 출처 - 상점명"]
+pub const SOURCE_STORE_NAME: &str = "store_name";
+#[doc = "자동완성 키워드의 출처 - 추천명"]
+pub const SOURCE_RECOMMEND_NAME: &str = "recommend_name";
+#[doc = "자동완성 키워드의 출처 - 음식점 대분류/소분류명"]
+pub const SOURCE_STORE_TYPE: &str = "store_type";
+#[doc = "자동완성 키워드의 출처 - 지역명 (시/군/구)"]
+pub const SOURCE_LOCATION: &str = "location";
+
+#[doc = "Elasticsearch 자동완성 인덱스와 mapping 할 구조체"]
+#[derive(Debug, Serialize, Getters, new)]
+#[getset(get = "pub")]
+pub struct AutoCompleteKeyword {
+    pub timestamp: String,
+    pub keyword: String,
+    pub weight: usize,
+    pub source_types: Vec<String>,
+}
+
+impl EsDocument for AutoCompleteKeyword {
+    #[doc = "중복이 제거된 키워드 자체를 문서의 _id 로 사용한다."]
+    fn document_id(&self) -> String {
+        self.keyword.clone()
+    }
+}
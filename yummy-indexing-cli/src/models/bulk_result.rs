@@ -0,0 +1,56 @@
+use crate::common::*;
+
+#[doc = "bulk 요청 중 색인에 실패한 개별 문서 정보"]
+#[derive(Debug, Clone, Serialize, Getters, new)]
+#[getset(get = "pub")]
+pub struct BulkItemFailure {
+    pub document_id: String,
+    pub status: u16,
+    pub error_type: String,
+    pub reason: String,
+    pub retryable: bool,
+}
+
+impl BulkItemFailure {
+    #[doc = "bulk 응답의 status 로 재시도 가능한 실패인지 판단해주는 함수 -> 429(rejected), 5xx 는 일시적인 실패로 본다."]
+    /// # Arguments
+    /// * `status` - bulk item 의 http status
+    ///
+    /// # Returns
+    /// * bool
+    pub fn is_retryable_status(status: u16) -> bool {
+        matches!(status, 429 | 500..=599)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn is_retryable_status_retries_only_rejected_and_server_errors() {
+        let status_table: [(u16, bool); 12] = [
+            (200, false),
+            (201, false),
+            (400, false),
+            (404, false),
+            (409, false),
+            (429, true),
+            (500, true),
+            (502, true),
+            (503, true),
+            (504, true),
+            (507, true),
+            (599, true),
+        ];
+
+        for (status, retryable) in status_table {
+            assert_eq!(
+                BulkItemFailure::is_retryable_status(status),
+                retryable,
+                "status {}",
+                status
+            );
+        }
+    }
+}
@@ -0,0 +1,12 @@
+use crate::common::*;
+
+#[doc = "Elasticsearch 에 색인될 문서가 구현해야 하는 trait - 문서의 고유 _id 를 정해준다."]
+pub trait EsDocument: Serialize + Send + Sync {
+    fn document_id(&self) -> String;
+}
+
+impl<T: EsDocument> EsDocument for &T {
+    fn document_id(&self) -> String {
+        (*self).document_id()
+    }
+}
@@ -0,0 +1,11 @@
+use crate::common::*;
+
+#[doc = "alias 에 맵핑될 수 있는 정적색인 인덱스 세대 정보 ({alias}-{YYYYmmddHHMMSS})"]
+#[derive(Debug, Clone, Serialize, Getters, new)]
+#[getset(get = "pub")]
+pub struct IndexGeneration {
+    pub index_name: String,
+    pub doc_count: u64,
+    pub creation_date: String,
+    pub is_alias_target: bool,
+}
@@ -0,0 +1,215 @@
+use crate::common::*;
+
+use crate::models::run_error::*;
+
+#[doc = "Elasticsearch 실패 응답 status 의 분류"]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EsStatusClass {
+    /* 400 -> 맵핑 충돌, 쿼리 파싱 오류 등 */
+    BadRequest,
+    /* 401, 403 */
+    Unauthorized,
+    /* 404 -> 인덱스/alias 가 존재하지 않는 경우 */
+    NotFound,
+    /* 409 -> 버전 충돌 */
+    Conflict,
+    /* 408 */
+    RequestTimeout,
+    /* 429 -> 요청 거절 */
+    TooManyRequests,
+    /* 502, 503, 504 */
+    Unavailable,
+    /* 그 외 5xx */
+    ServerError,
+    Other,
+}
+
+impl EsStatusClass {
+    #[doc = "http status 를 분류해주는 함수"]
+    /// # Arguments
+    /// * `status` - http status
+    ///
+    /// # Returns
+    /// * EsStatusClass
+    pub fn from_status(status: u16) -> Self {
+        match status {
+            400 => EsStatusClass::BadRequest,
+            401 | 403 => EsStatusClass::Unauthorized,
+            404 => EsStatusClass::NotFound,
+            408 => EsStatusClass::RequestTimeout,
+            409 => EsStatusClass::Conflict,
+            429 => EsStatusClass::TooManyRequests,
+            502..=504 => EsStatusClass::Unavailable,
+            500..=599 => EsStatusClass::ServerError,
+            _ => EsStatusClass::Other,
+        }
+    }
+
+    #[doc = "status 분류를 색인 작업 실패 원인으로 바꿔주는 함수"]
+    pub fn run_error_class(&self) -> RunErrorClass {
+        match self {
+            EsStatusClass::TooManyRequests => RunErrorClass::RateLimited,
+            EsStatusClass::RequestTimeout => RunErrorClass::Timeout,
+            EsStatusClass::Unavailable => RunErrorClass::Connection,
+            _ => RunErrorClass::Permanent,
+        }
+    }
+}
+
+#[doc = "Repository, Service 계층에서 사용하는 에러 -> 호출하는 쪽에서 실패 원인에 따라 재시도/알림/종료코드를 결정할 수 있다."]
+#[derive(Debug, thiserror::Error)]
+pub enum IndexingError {
+    /* Elasticsearch 가 실패 status 로 응답한 경우 */
+    #[error("[Elasticsearch Error][{function_name}] response status is failed ({status}): {error_body:?}")]
+    EsStatus {
+        function_name: String,
+        status: u16,
+        error_body: String,
+    },
+    /* 노드에 요청을 보내지 못했거나 응답을 받지 못한 경우 */
+    #[error("[Elasticsearch Error] {0}")]
+    EsTransport(#[from] EsError),
+    /* 모든 노드에 요청이 실패한 경우 -> 마지막 노드의 에러를 원인으로 남긴다. */
+    #[error("[Elasticsearch Error] All Elasticsearch nodes failed.")]
+    EsUnavailable {
+        #[source]
+        source: Option<Box<IndexingError>>,
+    },
+    /* connection pool 에서 유휴 connection 을 기다리다 시간이 초과된 경우 */
+    #[error("[Elasticsearch Error][get_elastic_conn()] Timed out after {waited:?} waiting for an idle Elasticsearch connection. (pool size: {pool_size})")]
+    EsPoolTimeout { waited: Duration, pool_size: usize },
+    /* 응답은 성공했지만 예상한 형태가 아닌 경우 */
+    #[error("[Elasticsearch Error][{function_name}] {reason}")]
+    EsInvalidResponse {
+        function_name: String,
+        reason: String,
+    },
+    #[error("[DB Error] {0}")]
+    Db(#[from] DbErr),
+    /* 설정 파일, 환경변수 등 설정이 잘못된 경우 */
+    #[error("[Config Error] {0}")]
+    Config(String),
+    /* 정적색인으로 새로 만든 인덱스가 검증을 통과하지 못해 alias 교체가 막힌 경우 */
+    #[error("[Validation Error][validate_new_index()] '{index_name}' {reason}")]
+    Validation { index_name: String, reason: String },
+    #[error("[Serialization Error] {0}")]
+    Serialization(#[from] serde_json::Error),
+    /* overlap_policy 'cancel' 또는 관리 API 로 실행중인 작업이 취소된 경우 */
+    #[error("[Canceled] {reason}")]
+    Canceled { reason: String },
+    #[error(transparent)]
+    Other(#[from] anyhow::Error),
+}
+
+impl IndexingError {
+    #[doc = "Elasticsearch 실패 응답인 경우 status 분류를 가져와주는 함수"]
+    /// # Returns
+    /// * Option<EsStatusClass> - Elasticsearch 실패 응답이 아니면 None
+    pub fn es_status_class(&self) -> Option<EsStatusClass> {
+        match self {
+            IndexingError::EsStatus { status, .. } => Some(EsStatusClass::from_status(*status)),
+            IndexingError::EsTransport(es_error) => es_error
+                .status_code()
+                .map(|status_code| EsStatusClass::from_status(status_code.as_u16())),
+            _ => None,
+        }
+    }
+
+    #[doc = "인덱스/alias 가 존재하지 않아서 실패한 것인지 확인해주는 함수"]
+    pub fn is_not_found(&self) -> bool {
+        self.es_status_class() == Some(EsStatusClass::NotFound)
+    }
+
+    #[doc = "에러를 색인 작업 실패 원인으로 분류해주는 함수 -> 스케쥴 재시도 여부의 기준이 된다."]
+    /// # Returns
+    /// * RunErrorClass
+    pub fn run_error_class(&self) -> RunErrorClass {
+        match self {
+            IndexingError::EsStatus { status, .. } => {
+                EsStatusClass::from_status(*status).run_error_class()
+            }
+            IndexingError::EsTransport(es_error) => RunErrorClass::classify_es_error(es_error),
+            IndexingError::EsUnavailable { source } => match source {
+                Some(source) => match source.run_error_class() {
+                    RunErrorClass::Permanent => RunErrorClass::Connection,
+                    run_error_class => run_error_class,
+                },
+                None => RunErrorClass::Connection,
+            },
+            IndexingError::EsPoolTimeout { .. } => RunErrorClass::Timeout,
+            IndexingError::EsInvalidResponse { .. } => RunErrorClass::Permanent,
+            IndexingError::Db(db_err) => RunErrorClass::classify_cause_chain(db_err),
+            IndexingError::Config(_) => RunErrorClass::Permanent,
+            IndexingError::Validation { .. } => RunErrorClass::Validation,
+            IndexingError::Serialization(_) => RunErrorClass::Permanent,
+            /* 취소된 작업은 재시도하지 않는다. */
+            IndexingError::Canceled { .. } => RunErrorClass::Permanent,
+            IndexingError::Other(error) => RunErrorClass::classify(error),
+        }
+    }
+
+    #[doc = "알림에 붙여줄 실패 원인 요약을 만들어주는 함수"]
+    /// # Returns
+    /// * String
+    pub fn summary(&self) -> String {
+        match self {
+            IndexingError::EsStatus {
+                function_name,
+                status,
+                ..
+            } => format!(
+                "Elasticsearch responded {} ({:?}) to {}",
+                status,
+                EsStatusClass::from_status(*status),
+                function_name
+            ),
+            IndexingError::EsTransport(_) => "Elasticsearch transport error".to_string(),
+            IndexingError::EsUnavailable { .. } => "All Elasticsearch nodes failed".to_string(),
+            IndexingError::EsPoolTimeout { .. } => {
+                "Timed out waiting for an Elasticsearch connection".to_string()
+            }
+            IndexingError::EsInvalidResponse { function_name, .. } => {
+                format!("Unexpected Elasticsearch response to {}", function_name)
+            }
+            IndexingError::Db(_) => "MySQL error".to_string(),
+            IndexingError::Config(_) => "Configuration error".to_string(),
+            IndexingError::Validation { index_name, .. } => {
+                format!("'{}' failed validation", index_name)
+            }
+            IndexingError::Serialization(_) => "Serialization error".to_string(),
+            IndexingError::Canceled { reason } => format!("Canceled ({})", reason),
+            IndexingError::Other(_) => "Unclassified error".to_string(),
+        }
+    }
+}
+
+#[doc = "에러 체인에서 가장 바깥쪽 IndexingError 를 찾아주는 함수"]
+/// # Arguments
+/// * `error` - 색인 작업 에러
+///
+/// # Returns
+/// * Option<&IndexingError>
+pub fn find_indexing_error(error: &anyhow::Error) -> Option<&IndexingError> {
+    let indexing_error: &IndexingError = error
+        .chain()
+        .find_map(|cause| cause.downcast_ref::<IndexingError>())?;
+
+    /* anyhow 에러를 감싼 경우에는 안쪽의 분류된 에러를 우선한다. */
+    match indexing_error {
+        IndexingError::Other(inner) => find_indexing_error(inner).or(Some(indexing_error)),
+        _ => Some(indexing_error),
+    }
+}
+
+#[doc = "색인 작업이 취소되어 끝난 에러인지 확인해주는 함수"]
+/// # Arguments
+/// * `error` - 색인 작업 에러
+///
+/// # Returns
+/// * bool
+pub fn is_canceled_error(error: &anyhow::Error) -> bool {
+    matches!(
+        find_indexing_error(error),
+        Some(IndexingError::Canceled { .. })
+    )
+}
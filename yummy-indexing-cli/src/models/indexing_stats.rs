@@ -0,0 +1,53 @@
+use crate::common::*;
+
+#[doc = "색인 파이프라인 한 단계(MySQL 읽기 / Elasticsearch 쓰기)의 처리 통계"]
+#[derive(Debug, Clone, Copy, Default, Serialize, Getters)]
+#[getset(get = "pub")]
+pub struct StageStats {
+    pub batch_cnt: usize,
+    pub document_cnt: usize,
+    pub failed_cnt: usize,
+    pub elapsed_ms: u128,
+}
+
+impl StageStats {
+    #[doc = "배치 하나의 처리 결과를 통계에 더해주는 함수"]
+    /// # Arguments
+    /// * `document_cnt` - 배치에서 처리한 문서 수
+    /// * `failed_cnt` - 배치에서 최종 실패한 문서 수
+    /// * `started_at` - 배치 처리 시작 시각
+    pub fn record_batch(&mut self, document_cnt: usize, failed_cnt: usize, started_at: Instant) {
+        self.batch_cnt += 1;
+        self.document_cnt += document_cnt;
+        self.failed_cnt += failed_cnt;
+        self.elapsed_ms += started_at.elapsed().as_millis();
+    }
+}
+
+impl Display for StageStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "batches: {}, documents: {}, failed: {}, elapsed: {}ms",
+            self.batch_cnt, self.document_cnt, self.failed_cnt, self.elapsed_ms
+        )
+    }
+}
+
+#[doc = "스트리밍 색인 파이프라인의 단계별 처리 통계"]
+#[derive(Debug, Clone, Copy, Default, Serialize, Getters)]
+#[getset(get = "pub")]
+pub struct IndexingStats {
+    pub read_stage: StageStats,
+    pub write_stage: StageStats,
+}
+
+impl Display for IndexingStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "[mysql read] {} | [es write] {}",
+            self.read_stage, self.write_stage
+        )
+    }
+}
@@ -1,2 +1,11 @@
+pub mod auto_complete;
+pub mod bulk_result;
+pub mod es_document;
+pub mod index_generation;
+pub mod indexing_error;
+pub mod indexing_stats;
+pub mod run_error;
+pub mod run_report;
+pub mod scheduler_state;
 pub mod store_to_elastic;
 pub mod store_types;
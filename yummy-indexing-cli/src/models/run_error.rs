@@ -0,0 +1,169 @@
+use crate::common::*;
+
+use crate::models::indexing_error::*;
+
+#[doc = "색인 작업 실패 원인의 분류 -> 스케쥴 재시도 여부를 판단하는 기준이 된다."]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RunErrorClass {
+    /* Elasticsearch 노드, MySQL 연결 실패 및 502/503/504 응답 */
+    Connection,
+    /* 요청 시간 초과 및 커넥션 풀 대기 시간 초과 */
+    Timeout,
+    /* Elasticsearch 429 응답 (요청 거절) */
+    RateLimited,
+    /* 정적색인 인덱스가 문서 수 검증을 통과하지 못해 alias 교체가 막힌 경우 */
+    Validation,
+    /* 맵핑 충돌 등 재시도해도 같은 결과가 나오는 실패 */
+    Permanent,
+}
+
+impl RunErrorClass {
+    #[doc = "실행 이력 테이블의 'error_class' 값으로 변환해주는 함수"]
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RunErrorClass::Connection => "connection",
+            RunErrorClass::Timeout => "timeout",
+            RunErrorClass::RateLimited => "rate_limited",
+            RunErrorClass::Validation => "validation",
+            RunErrorClass::Permanent => "permanent",
+        }
+    }
+
+    #[doc = "색인 작업의 에러를 분류해주는 함수 -> 에러 체인을 바깥쪽부터 확인하여 처음 분류되는 원인을 따른다."]
+    /// # Arguments
+    /// * `error` - 색인 작업 에러
+    ///
+    /// # Returns
+    /// * RunErrorClass - 분류할 수 없는 에러는 Permanent 로 본다.
+    pub fn classify(error: &anyhow::Error) -> RunErrorClass {
+        error
+            .chain()
+            .find_map(Self::classify_cause)
+            .unwrap_or(RunErrorClass::Permanent)
+    }
+
+    #[doc = "에러와 그 원인들을 분류해주는 함수 -> DB 에러처럼 원인 체인을 함께 봐야 하는 경우에 사용한다."]
+    /// # Arguments
+    /// * `error` - 분류할 에러
+    ///
+    /// # Returns
+    /// * RunErrorClass - 분류할 수 없는 에러는 Permanent 로 본다.
+    pub fn classify_cause_chain(error: &(dyn StdError + 'static)) -> RunErrorClass {
+        let mut cause: Option<&(dyn StdError + 'static)> = Some(error);
+
+        while let Some(current) = cause {
+            if let Some(run_error_class) = Self::classify_cause(current) {
+                return run_error_class;
+            }
+            cause = current.source();
+        }
+
+        RunErrorClass::Permanent
+    }
+
+    #[doc = "에러 체인의 원인 하나를 분류해주는 함수"]
+    /// # Arguments
+    /// * `cause` - 에러 체인의 원인
+    ///
+    /// # Returns
+    /// * Option<RunErrorClass> - 알 수 없는 타입의 원인이면 None
+    fn classify_cause(cause: &(dyn StdError + 'static)) -> Option<RunErrorClass> {
+        if let Some(indexing_error) = cause.downcast_ref::<IndexingError>() {
+            return Some(indexing_error.run_error_class());
+        }
+
+        if let Some(es_error) = cause.downcast_ref::<EsError>() {
+            return Some(Self::classify_es_error(es_error));
+        }
+
+        if let Some(db_err) = cause.downcast_ref::<DbErr>() {
+            return match db_err {
+                DbErr::ConnectionAcquire(ConnAcquireErr::Timeout) => Some(RunErrorClass::Timeout),
+                DbErr::ConnectionAcquire(_) | DbErr::Conn(_) => Some(RunErrorClass::Connection),
+                /* Exec, Query 에러는 원인인 sqlx 에러로 판단한다. */
+                _ => None,
+            };
+        }
+
+        if let Some(sqlx_error) = cause.downcast_ref::<SqlxError>() {
+            return match sqlx_error {
+                SqlxError::PoolTimedOut => Some(RunErrorClass::Timeout),
+                SqlxError::Io(_) | SqlxError::PoolClosed | SqlxError::WorkerCrashed => {
+                    Some(RunErrorClass::Connection)
+                }
+                _ => None,
+            };
+        }
+
+        if let Some(io_error) = cause.downcast_ref::<io::Error>() {
+            return match io_error.kind() {
+                io::ErrorKind::TimedOut => Some(RunErrorClass::Timeout),
+                io::ErrorKind::ConnectionRefused
+                | io::ErrorKind::ConnectionReset
+                | io::ErrorKind::ConnectionAborted
+                | io::ErrorKind::NotConnected
+                | io::ErrorKind::BrokenPipe
+                | io::ErrorKind::UnexpectedEof => Some(RunErrorClass::Connection),
+                /* 권한, 디스크 부족 등 로컬 파일 에러는 재시도해도 해결되지 않는다. */
+                _ => Some(RunErrorClass::Permanent),
+            };
+        }
+
+        None
+    }
+
+    #[doc = "Elasticsearch client 에러를 분류해주는 함수"]
+    /// # Arguments
+    /// * `es_error` - Elasticsearch client 에러
+    ///
+    /// # Returns
+    /// * RunErrorClass
+    pub fn classify_es_error(es_error: &EsError) -> RunErrorClass {
+        if es_error.is_timeout() {
+            return RunErrorClass::Timeout;
+        }
+
+        match es_error.status_code() {
+            Some(status_code) => EsStatusClass::from_status(status_code.as_u16()).run_error_class(),
+            /* 응답을 받지 못한 경우 -> 노드에 연결하지 못한 것으로 본다. */
+            None if !es_error.is_json() => RunErrorClass::Connection,
+            None => RunErrorClass::Permanent,
+        }
+    }
+
+    #[doc = "CLI 실행이 실패한 경우의 종료코드 -> 호출하는 쪽(cron, CI)에서 재실행 여부를 판단할 수 있도록 sysexits 값을 따른다."]
+    /// # Returns
+    /// * u8
+    pub fn exit_code(&self) -> u8 {
+        match self {
+            /* EX_UNAVAILABLE */
+            RunErrorClass::Connection => 69,
+            /* EX_TEMPFAIL */
+            RunErrorClass::Timeout | RunErrorClass::RateLimited => 75,
+            /* EX_DATAERR */
+            RunErrorClass::Validation => 65,
+            RunErrorClass::Permanent => 1,
+        }
+    }
+}
+
+#[doc = "CLI 실행 에러에 맞는 종료코드를 만들어주는 함수 -> 설정 오류는 EX_CONFIG(78) 로 구분한다."]
+/// # Arguments
+/// * `error` - 실행 에러
+///
+/// # Returns
+/// * ExitCode
+pub fn get_exit_code(error: &anyhow::Error) -> ExitCode {
+    if let Some(IndexingError::Config(_)) = find_indexing_error(error) {
+        return ExitCode::from(78);
+    }
+
+    ExitCode::from(RunErrorClass::classify(error).exit_code())
+}
+
+impl Display for RunErrorClass {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
@@ -0,0 +1,56 @@
+use crate::common::*;
+
+#[doc = "실행 이력 상태 - 색인 작업 진행중 (프로세스가 비정상 종료되면 이 상태로 남는다.)"]
+pub const RUN_STATUS_RUNNING: &str = "running";
+#[doc = "실행 이력 상태 - 색인 작업 성공"]
+pub const RUN_STATUS_SUCCESS: &str = "success";
+#[doc = "실행 이력 상태 - 색인 작업 실패"]
+pub const RUN_STATUS_FAILED: &str = "failed";
+#[doc = "실행 이력 상태 - overlap_policy 'cancel' 또는 관리 API 로 실행중인 작업이 취소됨"]
+pub const RUN_STATUS_CANCELED: &str = "canceled";
+
+#[doc = "색인 작업을 실행시킨 주체"]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum RunTrigger {
+    Schedule,
+    CatchUp,
+    Api,
+    Cli,
+}
+
+impl RunTrigger {
+    #[doc = "실행 이력 테이블의 'run_trigger' 값으로 변환해주는 함수"]
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RunTrigger::Schedule => "schedule",
+            RunTrigger::CatchUp => "catch_up",
+            RunTrigger::Api => "api",
+            RunTrigger::Cli => "cli",
+        }
+    }
+}
+
+#[doc = "색인 작업 한번의 처리 결과 -> 실패한 경우에도 실패 시점까지의 통계가 남는다."]
+#[derive(Debug, Clone, Default, Serialize, Getters)]
+#[getset(get = "pub")]
+pub struct RunReport {
+    pub rows_read: usize,
+    pub documents_written: usize,
+    pub documents_deleted: usize,
+    pub documents_failed: usize,
+    pub generated_index_name: Option<String>,
+}
+
+impl Display for RunReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "read: {}, written: {}, deleted: {}, failed: {}, index: {}",
+            self.rows_read,
+            self.documents_written,
+            self.documents_deleted,
+            self.documents_failed,
+            self.generated_index_name.as_deref().unwrap_or("-")
+        )
+    }
+}
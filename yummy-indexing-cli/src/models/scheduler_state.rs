@@ -0,0 +1,130 @@
+use crate::common::*;
+
+use crate::configuration::index_schedules_config::*;
+
+use crate::models::indexing_error::*;
+use crate::models::run_report::*;
+
+#[doc = "관리 API 에서 인덱스 스케쥴러로 보내는 명령"]
+#[derive(Debug, Clone)]
+pub enum SchedulerCommand {
+    /* 해당 색인 함수를 바로 실행한다. (겹치는 경우 overlap_policy 를 따른다.) */
+    Trigger(String),
+    /* 실행중인 작업에 취소 신호를 보낸다. (lock 해제, 실행 이력 기록을 마친 뒤 끝난다.) */
+    Cancel,
+    /* 해당 색인 함수의 스케쥴을 멈춘다. (None 이면 인덱스의 모든 스케쥴) */
+    Pause(Option<String>),
+    /* 멈춘 스케쥴을 다시 시작한다. (None 이면 인덱스의 모든 스케쥴) */
+    Resume(Option<String>),
+}
+
+#[doc = "스케쥴러 명령과 처리 결과를 돌려받을 채널"]
+#[derive(Debug, new)]
+pub struct SchedulerRequest {
+    pub command: SchedulerCommand,
+    pub reply: oneshot::Sender<Result<String, anyhow::Error>>,
+}
+
+#[doc = "스케쥴러 루프에서 관리하는 스케쥴 하나의 상태"]
+#[derive(Debug, Clone, new)]
+pub struct ScheduleEntry {
+    pub index_schedule: IndexSchedules,
+    pub schedule: Schedule,
+    pub timezone: Tz,
+    /* 마지막 실행 시각 -> 다음 실행 시각은 이 시각 이후로 계산한다. */
+    pub last_fire: DateTime<Utc>,
+    #[new(default)]
+    pub paused: bool,
+    #[new(default)]
+    pub last_result: Option<ScheduleRunResult>,
+}
+
+impl ScheduleEntry {
+    #[doc = "다음 실행 시각을 구해주는 함수 -> 해당 timezone 의 현지 시각으로 cron 을 계산하므로 DST 전환도 반영된다."]
+    /// # Returns
+    /// * Option<DateTime<Utc>> - 멈춘 스케쥴이거나 다음 실행 시각이 없으면 None
+    pub fn next_fire(&self) -> Option<DateTime<Utc>> {
+        if self.paused {
+            return None;
+        }
+
+        self.schedule
+            .after(&self.last_fire.with_timezone(&self.timezone))
+            .next()
+            .map(|next_fire| next_fire.with_timezone(&Utc))
+    }
+}
+
+#[doc = "스케쥴러가 실행한 작업의 마지막 결과"]
+#[derive(Debug, Clone, Serialize, Getters, new)]
+#[getset(get = "pub")]
+pub struct ScheduleRunResult {
+    pub finished_at: String,
+    pub run_trigger: String,
+    pub run_status: String,
+    pub message: String,
+}
+
+#[doc = "관리 API 로 보여줄 스케쥴 상태"]
+#[derive(Debug, Clone, Serialize, Getters)]
+#[getset(get = "pub")]
+pub struct ScheduleStatus {
+    pub index_name: String,
+    pub function_name: String,
+    pub indexing_type: String,
+    pub cron: String,
+    pub timezone: String,
+    pub paused: bool,
+    pub running: bool,
+    pub queued: bool,
+    pub next_fire: Option<String>,
+    pub last_result: Option<ScheduleRunResult>,
+}
+
+#[doc = "스케쥴러가 색인 작업 실행 요청을 처리한 방식"]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RunDispatch {
+    Started,
+    Queued,
+    AlreadyQueued,
+    Skipped,
+    Replaced,
+}
+
+impl RunDispatch {
+    #[doc = "관리 API 응답 문자열로 변환해주는 함수"]
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RunDispatch::Started => "started",
+            RunDispatch::Queued => "queued",
+            RunDispatch::AlreadyQueued => "already queued",
+            RunDispatch::Skipped => "skipped",
+            RunDispatch::Replaced => "queued (the previous run is being canceled)",
+        }
+    }
+}
+
+#[doc = "실행 결과를 관리 API 에서 보여줄 형태로 만들어주는 함수"]
+/// # Arguments
+/// * `run_trigger` - 색인 작업을 실행시킨 주체
+/// * `run_result` - 색인 작업 결과
+///
+/// # Returns
+/// * ScheduleRunResult
+pub fn create_schedule_run_result(
+    run_trigger: RunTrigger,
+    run_result: &Result<RunReport, anyhow::Error>,
+) -> ScheduleRunResult {
+    let (run_status, message) = match run_result {
+        Ok(run_report) => (RUN_STATUS_SUCCESS, run_report.to_string()),
+        Err(e) if is_canceled_error(e) => (RUN_STATUS_CANCELED, format!("{:#}", e)),
+        Err(e) => (RUN_STATUS_FAILED, format!("{:#}", e)),
+    };
+
+    ScheduleRunResult::new(
+        Utc::now().to_rfc3339(),
+        run_trigger.as_str().to_string(),
+        run_status.to_string(),
+        message,
+    )
+}
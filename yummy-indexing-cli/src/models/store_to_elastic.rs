@@ -1,5 +1,7 @@
 use crate::common::*;
 
+use crate::models::es_document::*;
+
 #[doc = "MySQL 와 맵핑할 구조체"]
 #[derive(Debug, FromQueryResult)]
 pub struct StoreResult {
@@ -19,7 +21,7 @@ pub struct StoreResult {
 }
 
 #[doc = "Elasticsearch 와 mapping 할 구조체"]
-#[derive(Debug, Serialize, Setters, new)]
+#[derive(Debug, Serialize, Setters)]
 #[getset(get = "pub", set = "pub")]
 pub struct DistinctStoreResult {
     pub timestamp: String,
@@ -29,6 +31,8 @@ pub struct DistinctStoreResult {
     pub address: Option<String>,
     pub lat: Decimal,
     pub lng: Decimal,
+    pub location: Option<GeoPoint>,
+    pub location_valid: bool,
     pub zero_possible: bool,
     pub recommend_names: Vec<String>,
     pub location_city: Option<String>,
@@ -36,4 +40,119 @@ pub struct DistinctStoreResult {
     pub location_district: Option<String>,
     pub major_type: Vec<i32>,
     pub sub_type: Vec<i32>,
+    pub major_type_name: Vec<String>,
+    pub sub_type_name: Vec<String>,
+}
+
+impl DistinctStoreResult {
+    #[doc = "MySQL 조회 결과 한 행으로 색인 문서를 만들어주는 함수 -> 대분류/소분류 정보는 이후에 따로 채워준다."]
+    /// # Arguments
+    /// * `store` - MySQL 조회 결과
+    /// * `timestamp` - 색인 시각
+    /// * `location` - 검증된 geo_point 좌표
+    ///
+    /// # Returns
+    /// * DistinctStoreResult
+    pub fn from_store_result(
+        store: &StoreResult,
+        timestamp: String,
+        location: Option<GeoPoint>,
+    ) -> Self {
+        DistinctStoreResult {
+            timestamp,
+            seq: store.seq,
+            name: store.name.clone(),
+            r#type: store.r#type.clone(),
+            address: store.address.clone(),
+            lat: store.lat,
+            lng: store.lng,
+            location,
+            location_valid: location.is_some(),
+            zero_possible: store.zero_possible,
+            recommend_names: store.recommend_name.clone().into_iter().collect(),
+            location_city: store.location_city.clone(),
+            location_county: store.location_county.clone(),
+            location_district: store.location_district.clone(),
+            major_type: Vec::new(),
+            sub_type: Vec::new(),
+            major_type_name: Vec::new(),
+            sub_type_name: Vec::new(),
+        }
+    }
+}
+
+#[doc = "Elasticsearch geo_point 필드와 mapping 할 구조체"]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct GeoPoint {
+    pub lat: f64,
+    pub lon: f64,
+}
+
+impl GeoPoint {
+    #[doc = "위도/경도 값으로 geo_point 를 만들어주는 함수 -> 범위를 벗어나거나 (0,0) 인 좌표는 None 을 반환한다."]
+    /// # Arguments
+    /// * `lat` - 위도
+    /// * `lng` - 경도
+    ///
+    /// # Returns
+    /// * Option<GeoPoint>
+    pub fn from_decimal(lat: Decimal, lng: Decimal) -> Option<GeoPoint> {
+        let lat: f64 = f64::try_from(lat).ok()?;
+        let lon: f64 = f64::try_from(lng).ok()?;
+
+        /* 좌표가 입력되지 않은 상점은 (0,0) 으로 저장되어 있으므로 위치정보가 없는 것으로 본다. */
+        if lat == 0.0 && lon == 0.0 {
+            return None;
+        }
+
+        if !(-90.0..=90.0).contains(&lat) || !(-180.0..=180.0).contains(&lon) {
+            return None;
+        }
+
+        Some(GeoPoint { lat, lon })
+    }
+}
+
+impl EsDocument for DistinctStoreResult {
+    #[doc = "상점 고유번호(seq)를 문서의 _id 로 사용한다."]
+    fn document_id(&self) -> String {
+        self.seq.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn geo_point_from_decimal_skips_only_missing_and_out_of_range_coordinates() {
+        /* 좌표가 입력되지 않은 (0,0) */
+        assert_eq!(GeoPoint::from_decimal(Decimal::ZERO, Decimal::ZERO), None);
+
+        /* 적도, 본초자오선 위의 좌표는 정상 좌표다. */
+        assert_eq!(
+            GeoPoint::from_decimal(Decimal::ZERO, Decimal::new(1269780000, 7)),
+            Some(GeoPoint {
+                lat: 0.0,
+                lon: 126.978
+            })
+        );
+        assert_eq!(
+            GeoPoint::from_decimal(Decimal::new(515007000, 7), Decimal::ZERO),
+            Some(GeoPoint {
+                lat: 51.5007,
+                lon: 0.0
+            })
+        );
+
+        /* 범위를 벗어난 좌표 */
+        assert_eq!(
+            GeoPoint::from_decimal(Decimal::new(905000000, 7), Decimal::new(1269780000, 7)),
+            None
+        );
+        assert_eq!(
+            GeoPoint::from_decimal(Decimal::new(375665000, 7), Decimal::new(-1805000000, 7)),
+            None
+        );
+    }
 }
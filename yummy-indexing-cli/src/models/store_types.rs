@@ -6,6 +6,8 @@ pub struct StoreTypesResult {
     pub seq: i32,
     pub major_type: i32,
     pub sub_type: i32,
+    pub major_type_name: String,
+    pub sub_type_name: String,
 }
 
 #[doc = "음식점 대분류 정보, 소분류 정보 를 가지고 있는 객체"]
@@ -13,4 +15,6 @@ pub struct StoreTypesResult {
 pub struct StoreTypesMap {
     pub store_type_major_map: HashMap<i32, Vec<i32>>,
     pub store_type_sub_map: HashMap<i32, Vec<i32>>,
+    pub store_type_major_name_map: HashMap<i32, Vec<String>>,
+    pub store_type_sub_name_map: HashMap<i32, Vec<String>>,
 }
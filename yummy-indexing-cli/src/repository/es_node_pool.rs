@@ -0,0 +1,492 @@
+use crate::common::*;
+
+use crate::configuration::es_connection_config::*;
+
+use crate::models::indexing_error::*;
+
+use crate::repository::es_repository::*;
+
+use crate::utils_module::metrics_utils::*;
+
+#[doc = "Elasticsearch 노드들의 상태를 전역으로 공유해주는 변수 -> 모든 connection 이 같은 노드 상태를 바라본다."]
+static ES_NODE_POOL: std::sync::OnceLock<Arc<EsNodePool>> = std::sync::OnceLock::new();
+
+#[doc = "환경변수로 Elasticsearch 노드 목록을 만들어주는 함수 -> Elasticsearch 를 사용하기 전에 한번 호출해야 한다."]
+/// 설정이 잘못된 경우 panic 대신 에러를 반환하여 EX_CONFIG(78) 로 종료할 수 있게 한다.
+///
+/// # Returns
+/// * Result<(), IndexingError>
+pub fn initialize_es_node_pool() -> Result<(), IndexingError> {
+    if ES_NODE_POOL.get().is_some() {
+        return Ok(());
+    }
+
+    let es_host: Vec<String> = env::var("ES_DB_URL")
+        .map_err(|_| {
+            IndexingError::Config(
+                "[ENV file read Error][initialize_es_node_pool()] 'ES_DB_URL' must be set"
+                    .to_string(),
+            )
+        })?
+        .split(',')
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+        .collect();
+
+    let connection_config: EsConnectionConfig =
+        EsConnectionConfig::from_env().map_err(|e| IndexingError::Config(format!("{:#}", e)))?;
+
+    let node_pool: EsNodePool = EsNodePool::new(es_host, connection_config)?;
+
+    /* 동시에 초기화된 경우 먼저 만들어진 노드 목록을 사용한다. */
+    let _ = ES_NODE_POOL.set(Arc::new(node_pool));
+
+    Ok(())
+}
+
+#[doc = "격리(quarantine) 기본 시간(초) -> 연속으로 실패할 때마다 두배씩 늘어난다."]
+const DEFAULT_QUARANTINE_SECS: u64 = 5;
+
+#[doc = "격리 시간의 상한(초)"]
+const DEFAULT_QUARANTINE_MAX_SECS: u64 = 300;
+
+#[doc = "노드 health check 주기(초)"]
+const DEFAULT_HEALTH_CHECK_INTERVAL_SECS: u64 = 10;
+
+#[doc = "노드 목록 sniffing 주기(초)"]
+const DEFAULT_SNIFF_INTERVAL_SECS: u64 = 60;
+
+#[doc = "Elasticsearch 노드 하나의 상태"]
+#[derive(Debug, Clone)]
+struct EsNode {
+    es_client: EsClient,
+    healthy: bool,
+    consecutive_failures: u32,
+    /* 격리된 노드를 다시 시도해볼 수 있는 시각 */
+    retry_at: Option<Instant>,
+    /* sniffing 으로 찾은 노드인지 여부 -> 'ES_DB_URL' 에 설정된 노드는 sniffing 결과와 관계없이 유지한다. */
+    sniffed: bool,
+}
+
+#[doc = "health check 와 격리 상태를 관리하는 Elasticsearch 노드 목록"]
+#[derive(Debug)]
+pub struct EsNodePool {
+    nodes: std::sync::RwLock<Vec<EsNode>>,
+    /* sniffing 으로 찾은 노드의 client 를 만들 때도 사용한다. */
+    connection_config: EsConnectionConfig,
+    quarantine_base: Duration,
+    quarantine_max: Duration,
+}
+
+impl EsNodePool {
+    #[doc = "설정된 노드들로 노드 목록을 만들어주는 함수 -> 처음에는 모든 노드를 정상으로 본다."]
+    /// # Arguments
+    /// * `es_host` - Elasticsearch 노드 주소 목록
+    /// * `connection_config` - Elasticsearch 접속 설정
+    ///
+    /// # Returns
+    /// * Result<EsNodePool, IndexingError>
+    pub fn new(
+        es_host: Vec<String>,
+        connection_config: EsConnectionConfig,
+    ) -> Result<Self, IndexingError> {
+        if es_host.is_empty() {
+            return Err(IndexingError::Config(
+                "[Error][EsNodePool::new()] 'ES_DB_URL' does not contain any host.".to_string(),
+            ));
+        }
+
+        let mut nodes: Vec<EsNode> = Vec::new();
+
+        for host in es_host {
+            let es_client: EsClient = create_es_client(&host, &connection_config)?;
+            get_indexing_metrics().init_es_node(&host);
+            nodes.push(EsNode {
+                es_client,
+                healthy: true,
+                consecutive_failures: 0,
+                retry_at: None,
+                sniffed: false,
+            });
+        }
+
+        let quarantine_base: Duration = Duration::from_secs(get_env_secs(
+            "ES_NODE_QUARANTINE_SECS",
+            DEFAULT_QUARANTINE_SECS,
+        ));
+        let quarantine_max: Duration = Duration::from_secs(get_env_secs(
+            "ES_NODE_QUARANTINE_MAX_SECS",
+            DEFAULT_QUARANTINE_MAX_SECS,
+        ))
+        .max(quarantine_base);
+
+        Ok(EsNodePool {
+            nodes: std::sync::RwLock::new(nodes),
+            connection_config,
+            quarantine_base,
+            quarantine_max,
+        })
+    }
+
+    #[doc = "Elasticsearch 접속 설정을 가져와주는 함수"]
+    pub fn get_connection_config(&self) -> &EsConnectionConfig {
+        &self.connection_config
+    }
+
+    #[doc = "요청을 보낼 노드들의 client 를 시도할 순서대로 가져와주는 함수"]
+    /// 정상 노드들은 부하가 몰리지 않도록 섞어서 반환한다.
+    /// 모든 노드가 격리된 경우에는 요청이 하나도 시도되지 않는 일이 없도록
+    /// 격리된 노드들을 재시도 시각이 빠른 순서대로 반환한다. (단일 노드 클러스터 등)
+    ///
+    /// # Returns
+    /// * Result<Vec<EsClient>, IndexingError>
+    pub(crate) fn get_available_clients(&self) -> Result<Vec<EsClient>, IndexingError> {
+        let nodes = self.nodes.read().map_err(|e| {
+            IndexingError::Other(anyhow!("[Error][get_available_clients()] {:?}", e))
+        })?;
+
+        let mut healthy_clients: Vec<EsClient> = nodes
+            .iter()
+            .filter(|node| node.healthy)
+            .map(|node| node.es_client.clone())
+            .collect();
+
+        if !healthy_clients.is_empty() {
+            let mut rng: StdRng = StdRng::from_entropy();
+            healthy_clients.shuffle(&mut rng);
+            return Ok(healthy_clients);
+        }
+
+        warn!("[get_available_clients()] All Elasticsearch nodes are quarantined. Trying them in order of their retry time.");
+
+        let mut quarantined_nodes: Vec<&EsNode> = nodes.iter().collect();
+        quarantined_nodes.sort_by_key(|node| node.retry_at);
+
+        Ok(quarantined_nodes
+            .into_iter()
+            .map(|node| node.es_client.clone())
+            .collect())
+    }
+
+    #[doc = "노드 요청이 성공했음을 기록해주는 함수 -> 격리된 노드였다면 다시 정상으로 돌려준다."]
+    /// # Arguments
+    /// * `host` - 노드 주소
+    pub fn mark_success(&self, host: &str) {
+        let mut nodes = match self.nodes.write() {
+            Ok(nodes) => nodes,
+            Err(e) => {
+                error!("[Error][mark_success()] {:?}", e);
+                return;
+            }
+        };
+
+        if let Some(node) = nodes.iter_mut().find(|node| node.es_client.host() == host) {
+            if !node.healthy {
+                info!(
+                    "[EsNodePool] Elasticsearch node '{}' is healthy again after {} failure(s).",
+                    host, node.consecutive_failures
+                );
+                get_indexing_metrics().set_es_node_healthy(host, true);
+            }
+
+            node.healthy = true;
+            node.consecutive_failures = 0;
+            node.retry_at = None;
+        }
+    }
+
+    #[doc = "노드 요청이 실패했음을 기록해주는 함수 -> 연속 실패 횟수에 따라 격리 시간을 늘려준다."]
+    /// # Arguments
+    /// * `host` - 노드 주소
+    /// * `err` - 실패 원인
+    pub fn mark_failure(&self, host: &str, err: &dyn Debug) {
+        let mut nodes = match self.nodes.write() {
+            Ok(nodes) => nodes,
+            Err(e) => {
+                error!("[Error][mark_failure()] {:?}", e);
+                return;
+            }
+        };
+
+        if let Some(node) = nodes.iter_mut().find(|node| node.es_client.host() == host) {
+            node.consecutive_failures = node.consecutive_failures.saturating_add(1);
+
+            let quarantine: Duration = self.get_quarantine_duration(node.consecutive_failures);
+            node.retry_at = Some(Instant::now() + quarantine);
+
+            if node.healthy {
+                warn!(
+                    "[EsNodePool] Elasticsearch node '{}' is quarantined for {:?} : {:?}",
+                    host, quarantine, err
+                );
+                get_indexing_metrics().set_es_node_healthy(host, false);
+            } else {
+                warn!(
+                    "[EsNodePool] Elasticsearch node '{}' is still unavailable ({} consecutive failures). Next retry in {:?} : {:?}",
+                    host, node.consecutive_failures, quarantine, err
+                );
+            }
+
+            node.healthy = false;
+        }
+    }
+
+    #[doc = "연속 실패 횟수에 따른 격리 시간을 계산해주는 함수"]
+    /// # Arguments
+    /// * `consecutive_failures` - 연속 실패 횟수 (1 부터 시작)
+    ///
+    /// # Returns
+    /// * Duration
+    fn get_quarantine_duration(&self, consecutive_failures: u32) -> Duration {
+        let exponent: u32 = consecutive_failures.saturating_sub(1).min(16);
+        self.quarantine_base
+            .saturating_mul(2u32.pow(exponent))
+            .min(self.quarantine_max)
+    }
+
+    #[doc = "정상 노드와 재시도 시각이 된 격리 노드에 ping 을 보내 상태를 갱신해주는 함수"]
+    pub async fn check_nodes(&self) {
+        let now: Instant = Instant::now();
+
+        let check_targets: Vec<EsClient> = match self.nodes.read() {
+            Ok(nodes) => nodes
+                .iter()
+                .filter(|node| node.healthy || node.retry_at.is_none_or(|retry_at| retry_at <= now))
+                .map(|node| node.es_client.clone())
+                .collect(),
+            Err(e) => {
+                error!("[Error][check_nodes()] {:?}", e);
+                return;
+            }
+        };
+
+        for es_client in check_targets {
+            match es_client.es_conn().ping().send().await {
+                Ok(response) if response.status_code().is_success() => {
+                    self.mark_success(es_client.host());
+                }
+                Ok(response) => {
+                    let status: u16 = response.status_code().as_u16();
+                    self.mark_failure(
+                        es_client.host(),
+                        &anyhow!("ping responded with status {}", status),
+                    );
+                }
+                Err(e) => {
+                    self.mark_failure(es_client.host(), &anyhow!(e));
+                }
+            }
+        }
+    }
+
+    #[doc = "클러스터에서 HTTP 노드 목록을 가져와 새로운 노드를 추가해주는 함수"]
+    /// # Returns
+    /// * Result<(), anyhow::Error>
+    pub async fn sniff_nodes(&self) -> Result<(), anyhow::Error> {
+        let es_clients: Vec<EsClient> = self.get_available_clients()?;
+
+        let mut last_error: Option<anyhow::Error> = None;
+        let mut node_info: Option<Value> = None;
+
+        for es_client in es_clients {
+            let response: Result<Response, EsError> = es_client
+                .es_conn()
+                .nodes()
+                .info(NodesInfoParts::Metric(&["http"]))
+                .send()
+                .await;
+
+            match response {
+                Ok(response) if response.status_code().is_success() => {
+                    node_info = Some(response.json::<Value>().await?);
+                    break;
+                }
+                Ok(response) => {
+                    last_error = Some(anyhow!(
+                        "nodes info responded with status {}",
+                        response.status_code().as_u16()
+                    ));
+                }
+                Err(e) => last_error = Some(anyhow!(e)),
+            }
+        }
+
+        let node_info: Value = match node_info {
+            Some(node_info) => node_info,
+            None => {
+                return Err(anyhow!(
+                    "[Error][sniff_nodes()] Failed to get the node list : {:?}",
+                    last_error
+                ))
+            }
+        };
+
+        let sniffed_hosts: Vec<String> = node_info["nodes"]
+            .as_object()
+            .map(|nodes| {
+                nodes
+                    .values()
+                    .filter_map(|node| node["http"]["publish_address"].as_str())
+                    .map(get_publish_host)
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        /* 노드 목록을 받지 못한 경우 기존 노드를 모두 잃지 않도록 정리하지 않는다. */
+        if sniffed_hosts.is_empty() {
+            return Err(anyhow!(
+                "[Error][sniff_nodes()] The cluster did not report any HTTP node."
+            ));
+        }
+
+        self.remove_unlisted_nodes(&sniffed_hosts);
+
+        for host in sniffed_hosts {
+            let is_known: bool = match self.nodes.read() {
+                Ok(nodes) => nodes.iter().any(|node| *node.es_client.host() == host),
+                Err(e) => {
+                    error!("[Error][sniff_nodes()] {:?}", e);
+                    continue;
+                }
+            };
+
+            if is_known {
+                continue;
+            }
+
+            let es_client: EsClient = create_es_client(&host, &self.connection_config)?;
+
+            match self.nodes.write() {
+                Ok(mut nodes) => {
+                    if nodes.iter().any(|node| *node.es_client.host() == host) {
+                        continue;
+                    }
+
+                    info!("[EsNodePool] Discovered Elasticsearch node '{}'.", host);
+                    get_indexing_metrics().init_es_node(&host);
+                    nodes.push(EsNode {
+                        es_client,
+                        healthy: true,
+                        consecutive_failures: 0,
+                        retry_at: None,
+                        sniffed: true,
+                    });
+                }
+                Err(e) => error!("[Error][sniff_nodes()] {:?}", e),
+            }
+        }
+
+        Ok(())
+    }
+
+    #[doc = "sniffing 으로 찾았던 노드 중 클러스터가 더 이상 알려주지 않는 노드를 빼주는 함수"]
+    /// # Arguments
+    /// * `sniffed_hosts` - 이번 sniffing 에서 받은 노드 주소 목록
+    fn remove_unlisted_nodes(&self, sniffed_hosts: &[String]) {
+        let mut nodes = match self.nodes.write() {
+            Ok(nodes) => nodes,
+            Err(e) => {
+                error!("[Error][remove_unlisted_nodes()] {:?}", e);
+                return;
+            }
+        };
+
+        nodes.retain(|node| {
+            let host: &String = node.es_client.host();
+
+            if !node.sniffed || sniffed_hosts.contains(host) {
+                return true;
+            }
+
+            info!(
+                "[EsNodePool] Elasticsearch node '{}' is no longer in the cluster.",
+                host
+            );
+            get_indexing_metrics().remove_es_node(host);
+            false
+        });
+    }
+}
+
+#[doc = "Elasticsearch 노드 목록을 가져와주는 함수"]
+pub fn get_es_node_pool() -> Arc<EsNodePool> {
+    match ES_NODE_POOL.get() {
+        Some(node_pool) => Arc::clone(node_pool),
+        None => {
+            error!("[Error][get_es_node_pool()] initialize_es_node_pool() must be called first.");
+            panic!("[Error][get_es_node_pool()] initialize_es_node_pool() must be called first.");
+        }
+    }
+}
+
+#[doc = "노드 health check 와 sniffing 을 주기적으로 실행해주는 함수 -> 데몬에서 하나만 띄워준다."]
+pub async fn run_es_node_health_check() {
+    let node_pool: Arc<EsNodePool> = get_es_node_pool();
+
+    let check_interval: Duration = Duration::from_secs(
+        get_env_secs(
+            "ES_HEALTH_CHECK_INTERVAL_SECS",
+            DEFAULT_HEALTH_CHECK_INTERVAL_SECS,
+        )
+        .max(1),
+    );
+
+    /* sniffing 은 'ES_SNIFF_NODES=true' 인 경우에만 실행한다. */
+    let sniff_interval: Option<Duration> = match env::var("ES_SNIFF_NODES") {
+        Ok(sniff_nodes) if sniff_nodes.trim().eq_ignore_ascii_case("true") => {
+            Some(Duration::from_secs(
+                get_env_secs("ES_SNIFF_INTERVAL_SECS", DEFAULT_SNIFF_INTERVAL_SECS).max(1),
+            ))
+        }
+        _ => {
+            info!("'ES_SNIFF_NODES' is not set. Elasticsearch node sniffing is disabled.");
+            None
+        }
+    };
+
+    let mut next_sniff: Instant = Instant::now();
+
+    loop {
+        if let Some(sniff_interval) = sniff_interval {
+            if Instant::now() >= next_sniff {
+                if let Err(e) = node_pool.sniff_nodes().await {
+                    error!("{:?}", e);
+                }
+                next_sniff = Instant::now() + sniff_interval;
+            }
+        }
+
+        node_pool.check_nodes().await;
+
+        tokio::time::sleep(check_interval).await;
+    }
+}
+
+#[doc = "초 단위 환경변수를 읽어주는 함수 -> 없거나 잘못된 값이면 기본값을 사용한다."]
+/// # Arguments
+/// * `env_name` - 환경변수 이름
+/// * `default_secs` - 기본값
+///
+/// # Returns
+/// * u64
+fn get_env_secs(env_name: &str, default_secs: u64) -> u64 {
+    env::var(env_name)
+        .ok()
+        .and_then(|value| value.trim().parse::<u64>().ok())
+        .unwrap_or(default_secs)
+}
+
+#[doc = "nodes info 의 publish_address 에서 접속 주소를 꺼내주는 함수"]
+/// publish_address 는 'hostname/ip:port' 또는 'ip:port' 형태이다.
+///
+/// # Arguments
+/// * `publish_address` - publish_address 값
+///
+/// # Returns
+/// * String
+fn get_publish_host(publish_address: &str) -> String {
+    match publish_address.split_once('/') {
+        Some((_, address)) => address.to_string(),
+        None => publish_address.to_string(),
+    }
+}
@@ -1,90 +1,119 @@
 use crate::common::*;
 
-use crate::utils_module::io_utils::*;
+use crate::models::bulk_result::*;
+use crate::models::es_document::*;
+use crate::models::indexing_error::*;
 
-#[doc = "Elasticsearch connection object to be used in a single tone"]
-static ELASTICSEARCH_CONN_POOL: once_lazy<Arc<Mutex<VecDeque<EsRepositoryPub>>>> =
-    once_lazy::new(|| Arc::new(Mutex::new(initialize_elastic_clients())));
+use crate::configuration::es_connection_config::*;
 
-#[doc = "Function to initialize Elasticsearch connection instances"]
-pub fn initialize_elastic_clients() -> VecDeque<EsRepositoryPub> {
-    info!("initialize_elastic_clients() START!");
+use crate::repository::es_node_pool::*;
+
+use crate::utils_module::metrics_utils::*;
+
+#[doc = "Elasticsearch connection pool to be used in a single tone"]
+static ELASTICSEARCH_CONN_POOL: once_lazy<EsConnPool> = once_lazy::new(initialize_elastic_pool);
+
+#[doc = "connection 을 기다리는 최대 시간(초)의 기본값"]
+const DEFAULT_POOL_ACQUIRE_TIMEOUT_SECS: u64 = 30;
+
+#[doc = "Elasticsearch connection pool -> 동시에 사용할 수 있는 connection 수를 semaphore 로 제한한다."]
+/// Elasticsearch client 는 내부적으로 thread-safe 하므로 하나를 공유하고,
+/// semaphore 의 permit 이 connection 하나의 사용권이 된다. (대기 순서대로 permit 을 받는다.)
+#[derive(Debug)]
+pub struct EsConnPool {
+    es_repo: EsRepositoryPub,
+    semaphore: Arc<Semaphore>,
+    pool_size: usize,
+    acquire_timeout: Duration,
+}
+
+impl EsConnPool {
+    #[doc = "사용중인 connection 수와 유휴 connection 수를 기록해주는 함수"]
+    fn record_pool_stats(&self) {
+        let idle_cnt: usize = self.semaphore.available_permits();
+        let in_use_cnt: usize = self.pool_size.saturating_sub(idle_cnt);
+
+        get_indexing_metrics().set_es_pool_idle(idle_cnt);
+        get_indexing_metrics().set_es_pool_in_use(in_use_cnt);
+        info!(
+            "[connection pool] Elasticsearch in_use = {:?}, idle = {:?}",
+            in_use_cnt, idle_cnt
+        );
+    }
+}
+
+#[doc = "Function to initialize Elasticsearch connection pool"]
+pub fn initialize_elastic_pool() -> EsConnPool {
+    info!("initialize_elastic_pool() START!");
 
     /* Number of Elasticsearch connection pool */
-    let pool_cnt: usize = match env::var("ES_POOL_CNT") {
-        Ok(pool_cnt) => pool_cnt.parse::<usize>().unwrap_or(3),
+    let pool_size: usize = match env::var("ES_POOL_CNT") {
+        Ok(pool_cnt) => pool_cnt.parse::<usize>().unwrap_or(3).max(1),
         Err(e) => {
-            error!("[Error][initialize_elastic_clients()] {:?}", e);
+            error!("[Error][initialize_elastic_pool()] {:?}", e);
             panic!("{:?}", e);
         }
     };
 
-    let es_host: Vec<String> = env::var("ES_DB_URL")
-        .expect("[ENV file read Error][initialize_db_clients()] 'ES_DB_URL' must be set")
-        .split(',')
-        .map(|s| s.to_string())
-        .collect();
-
-    let es_id = env::var("ES_ID")
-        .expect("[ENV file read Error][initialize_db_clients()] 'ES_ID' must be set");
-    let es_pw = env::var("ES_PW")
-        .expect("[ENV file read Error][initialize_db_clients()] 'ES_PW' must be set");
-
-    let mut es_pool_vec: VecDeque<EsRepositoryPub> = VecDeque::new();
-
-    for _conn_id in 0..pool_cnt {
-        /* Elasticsearch connection */
-        let es_connection: EsRepositoryPub = match EsRepositoryPub::new(
-            es_host.clone(),
-            &es_id,
-            &es_pw,
-        ) {
-            Ok(es_client) => es_client,
-            Err(err) => {
-                error!("[DB Connection Error][initialize_db_clients()] Failed to create Elasticsearch client : {:?}", err);
-                panic!("[DB Connection Error][initialize_db_clients()] Failed to create Elasticsearch client : {:?}", err);
-            }
-        };
+    /* 유휴 connection 을 기다리는 최대 시간 */
+    let acquire_timeout: Duration = Duration::from_secs(
+        env::var("ES_POOL_ACQUIRE_TIMEOUT_SECS")
+            .ok()
+            .and_then(|secs| secs.trim().parse::<u64>().ok())
+            .unwrap_or(DEFAULT_POOL_ACQUIRE_TIMEOUT_SECS),
+    );
 
-        es_pool_vec.push_back(es_connection);
-    }
+    /* 모든 connection 이 같은 노드 목록(health 상태)을 공유한다. */
+    let es_repo: EsRepositoryPub = EsRepositoryPub::new(get_es_node_pool());
+
+    get_indexing_metrics().set_es_pool_size(pool_size);
+    get_indexing_metrics().set_es_pool_idle(pool_size);
+    get_indexing_metrics().set_es_pool_in_use(0);
 
-    es_pool_vec
+    EsConnPool {
+        es_repo,
+        semaphore: Arc::new(Semaphore::new(pool_size)),
+        pool_size,
+        acquire_timeout,
+    }
 }
 
 #[doc = "Function to get elasticsearch connection"]
-async fn get_elastic_conn() -> Result<EsRepositoryPub, anyhow::Error> {
-    /* Elasticsearch Connection 이 부족한 경우를 대비하여 대기 시간을 걸어준다. */
-    for try_cnt in 1..=10 {
-        let es_repo: Option<EsRepositoryPub> = {
-            let mut pool: MutexGuard<'_, VecDeque<EsRepositoryPub>> =
-                ELASTICSEARCH_CONN_POOL.lock().await;
-
-            /* 여기서 pool.pop_front()가 실행된 후, pool은 스코프를 벗어나면서 자동 해제 */
-            let inner_pool: Option<EsRepositoryPub> = pool.pop_front();
-            info!(
-                "[connection get()] Elasticsearch pool.len = {:?}",
-                pool.len()
-            );
-
-            inner_pool
-        };
-
-        if let Some(es_repo) = es_repo {
-            return Ok(es_repo);
+/// 유휴 connection 이 없으면 먼저 기다린 순서대로 받게 되며, 'ES_POOL_ACQUIRE_TIMEOUT_SECS' 를 넘기면 실패한다.
+///
+/// # Returns
+/// * Result<ElasticConnGuard, IndexingError>
+async fn get_elastic_conn() -> Result<ElasticConnGuard, IndexingError> {
+    let pool: &'static EsConnPool = &ELASTICSEARCH_CONN_POOL;
+
+    let permit: OwnedSemaphorePermit = match tokio::time::timeout(
+        pool.acquire_timeout,
+        Arc::clone(&pool.semaphore).acquire_owned(),
+    )
+    .await
+    {
+        Ok(Ok(permit)) => permit,
+        Ok(Err(e)) => {
+            return Err(anyhow!(
+                "[Error][get_elastic_conn()] The Elasticsearch connection pool is closed. {:?}",
+                e
+            )
+            .into())
         }
+        Err(_) => {
+            return Err(IndexingError::EsPoolTimeout {
+                waited: pool.acquire_timeout,
+                pool_size: pool.pool_size,
+            })
+        }
+    };
 
-        warn!(
-            "[Attempt {}] The Elasticsearch connection pool does not have an idle connection.",
-            try_cnt
-        );
-
-        tokio::time::sleep(Duration::from_secs(7)).await;
-    }
+    pool.record_pool_stats();
 
-    return Err(anyhow!(
-        "[Error][get_elastic_conn()] Cannot Find Elasticsearch Connection"
-    ));
+    Ok(ElasticConnGuard {
+        pool,
+        permit: Some(permit),
+    })
 }
 
 #[async_trait]
@@ -93,187 +122,258 @@ pub trait EsRepository {
         &self,
         function_name: &str,
         response: Response,
-    ) -> Result<(), anyhow::Error>;
+    ) -> Result<(), IndexingError>;
     async fn process_response(
         &self,
         function_name: &str,
         response: Response,
-    ) -> Result<Value, anyhow::Error>;
+    ) -> Result<Value, IndexingError>;
+    async fn process_bulk_response(
+        &self,
+        function_name: &str,
+        response: Response,
+    ) -> Result<Vec<BulkItemFailure>, IndexingError>;
     async fn get_search_query(
         &self,
         es_query: &Value,
         index_name: &str,
-    ) -> Result<Value, anyhow::Error>;
-    async fn post_query(&self, document: &Value, index_name: &str) -> Result<(), anyhow::Error>;
-    async fn delete_query_doc(&self, doc_id: &str, index_name: &str) -> Result<(), anyhow::Error>;
-    async fn delete_query(&self, index_name: &str) -> Result<(), anyhow::Error>;
-    async fn delete_query_where_field(
+    ) -> Result<Value, IndexingError>;
+    async fn post_query(&self, document: &Value, index_name: &str) -> Result<(), IndexingError>;
+    async fn delete_query_doc(&self, doc_id: &str, index_name: &str) -> Result<(), IndexingError>;
+    async fn delete_query(&self, index_name: &str) -> Result<(), IndexingError>;
+    async fn delete_query_where_field_in(
         &self,
         index_name: &str,
         field_name: &str,
-        field_value: i32,
-    ) -> Result<(), anyhow::Error>;
+        field_values: &[i32],
+    ) -> Result<(), IndexingError>;
     async fn get_indexes_mapping_by_alias(
         &self,
         index_alias_name: &str,
-    ) -> Result<Value, anyhow::Error>;
+    ) -> Result<Value, IndexingError>;
     async fn update_index_alias(
         &self,
         index_alias: &str,
         new_index_name: &str,
         old_index_name: &str,
-    ) -> Result<(), anyhow::Error>;
+    ) -> Result<(), IndexingError>;
     async fn create_index_alias(
         &self,
         index_alias: &str,
         index_name: &str,
-    ) -> Result<(), anyhow::Error>;
-    async fn bulk_indexing_query<T: Serialize + Send + Sync>(
+    ) -> Result<(), IndexingError>;
+    async fn bulk_indexing_query<T: EsDocument>(
         &self,
         index_name: &str,
         data: &[T],
         batch_size: usize,
-    ) -> Result<(), anyhow::Error>;
+        max_bulk_bytes: Option<usize>,
+    ) -> Result<Vec<BulkItemFailure>, IndexingError>;
     async fn create_index(
         &self,
         index_name: &str,
         index_setting_json: &Value,
-    ) -> Result<(), anyhow::Error>;
-    async fn post_query_struct<T: Serialize + Sync>(
-        &self,
-        param_struct: &T,
-        index_name: &str,
-    ) -> Result<(), anyhow::Error>;
+    ) -> Result<(), IndexingError>;
     async fn get_scroll_initial_search_query(
         &self,
         index_name: &str,
         scroll_duration: &str,
         es_query: &Value,
-    ) -> Result<Value, anyhow::Error>;
+    ) -> Result<Value, IndexingError>;
     async fn get_scroll_search_query(
         &self,
         scroll_duration: &str,
         scroll_id: &str,
-    ) -> Result<Value, anyhow::Error>;
-    async fn clear_scroll_info(&self, scroll_id: &str) -> Result<(), anyhow::Error>;
-    async fn refresh_index(&self, index_name: &str) -> Result<(), anyhow::Error>;
-    async fn check_index_exist(&self, index_name: &str) -> Result<Value, anyhow::Error>;
+    ) -> Result<Value, IndexingError>;
+    async fn clear_scroll_info(&self, scroll_id: &str) -> Result<(), IndexingError>;
+    async fn refresh_index(&self, index_name: &str) -> Result<(), IndexingError>;
+    async fn check_index_exist(&self, index_name: &str) -> Result<Value, IndexingError>;
+    async fn get_index_count(&self, index_name: &str) -> Result<u64, IndexingError>;
+    async fn get_cat_indices(&self, index_pattern: &str) -> Result<Value, IndexingError>;
 }
 
-#[derive(Debug, Getters, Clone)]
+#[derive(Debug, Getters, Clone, new)]
 pub struct EsRepositoryPub {
-    es_clients: Vec<EsClient>,
+    node_pool: Arc<EsNodePool>,
 }
 
 #[derive(Debug, Getters, Clone, new)]
 pub(crate) struct EsClient {
+    #[getset(get = "pub(crate)")]
     host: String,
+    #[getset(get = "pub(crate)")]
     es_conn: Elasticsearch,
 }
 
-impl EsRepositoryPub {
-    pub fn new(es_url_vec: Vec<String>, es_id: &str, es_pw: &str) -> Result<Self, anyhow::Error> {
-        let mut es_clients: Vec<EsClient> = Vec::new();
-
-        for url in es_url_vec {
-            let parse_url: String = format!("http://{}:{}@{}", es_id, es_pw, url);
-            let es_url: Url = Url::parse(&parse_url)?;
-            let conn_pool: SingleNodeConnectionPool = SingleNodeConnectionPool::new(es_url);
-
-            let mut headers: HeaderMap = HeaderMap::new();
-            headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
-
-            let transport: Transport = TransportBuilder::new(conn_pool)
-                .timeout(Duration::new(5, 0))
-                .headers(headers)
-                .build()?;
-
-            let elastic_conn: Elasticsearch = Elasticsearch::new(transport);
-            let es_client: EsClient = EsClient::new(url, elastic_conn);
+#[doc = "Elasticsearch 노드 하나에 대한 client 를 만들어주는 함수"]
+/// # Arguments
+/// * `host` - 노드 주소
+/// * `connection_config` - Elasticsearch 접속 설정 (scheme, 인증, 인증서, 기본 timeout)
+///
+/// # Returns
+/// * Result<EsClient, IndexingError>
+pub(crate) fn create_es_client(
+    host: &str,
+    connection_config: &EsConnectionConfig,
+) -> Result<EsClient, IndexingError> {
+    /* 잘못된 주소, 인증서 등은 재시도해도 해결되지 않으므로 설정 오류(EX_CONFIG)로 처리한다. */
+    let es_url: Url = connection_config
+        .get_node_url(host)
+        .map_err(|e| IndexingError::Config(format!("{:#}", e)))?;
+    let conn_pool: SingleNodeConnectionPool = SingleNodeConnectionPool::new(es_url);
+
+    let mut headers: HeaderMap = HeaderMap::new();
+    headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+
+    /* 요청별 timeout 이 따로 지정되지 않은 요청(검색 등)은 기본 timeout 을 사용한다. */
+    let transport: Transport = TransportBuilder::new(conn_pool)
+        .auth(connection_config.get_credentials())
+        .cert_validation(
+            connection_config
+                .get_cert_validation()
+                .map_err(|e| IndexingError::Config(format!("{:#}", e)))?,
+        )
+        .timeout(*connection_config.request_timeout())
+        .headers(headers)
+        .build()
+        .map_err(|e| {
+            IndexingError::Config(format!(
+                "[Error][create_es_client()] Failed to build the transport for '{}': {}",
+                host, e
+            ))
+        })?;
 
-            es_clients.push(es_client);
-        }
+    let elastic_conn: Elasticsearch = Elasticsearch::new(transport);
 
-        Ok(EsRepositoryPub { es_clients })
-    }
+    Ok(EsClient::new(host.to_string(), elastic_conn))
+}
 
+impl EsRepositoryPub {
     #[doc = "Common logic: common node failure handling and node selection"]
-    async fn execute_on_any_node<F, Fut>(&self, operation: F) -> Result<Response, anyhow::Error>
+    async fn execute_on_any_node<F, Fut>(&self, operation: F) -> Result<Response, IndexingError>
     where
         F: Fn(EsClient) -> Fut + Send + Sync,
-        Fut: Future<Output = Result<Response, anyhow::Error>> + Send,
+        Fut: Future<Output = Result<Response, IndexingError>> + Send,
     {
-        let mut last_error: Option<anyhow::Error> = None;
+        let mut last_error: Option<IndexingError> = None;
 
-        let mut rng: StdRng = StdRng::from_entropy();
-        let mut shuffled_clients: Vec<EsClient> = self.es_clients.clone();
-        shuffled_clients.shuffle(&mut rng);
+        /* 정상 노드가 있으면 격리된 노드는 제외한다. -> 격리된 노드의 복구는 health check 가 확인한다. */
+        let es_clients: Vec<EsClient> = self.node_pool.get_available_clients()?;
+
+        for es_client in es_clients {
+            let host: String = es_client.host().clone();
 
-        for es_client in shuffled_clients {
             match operation(es_client).await {
-                Ok(response) => return Ok(response),
+                Ok(response) => {
+                    self.node_pool.mark_success(&host);
+                    return Ok(response);
+                }
                 Err(err) => {
+                    /* 노드가 응답한 Elasticsearch 오류는 노드 장애가 아니므로 격리하지 않는다. */
+                    if !matches!(err, IndexingError::EsStatus { .. }) {
+                        self.node_pool.mark_failure(&host, &err);
+                    }
                     last_error = Some(err);
                 }
             }
         }
 
-        Err(anyhow::anyhow!(
-            "All Elasticsearch nodes failed. Last error: {:?}",
-            last_error
-        ))
+        /* 마지막 에러를 원인으로 남겨두어야 호출하는 쪽에서 실패 원인을 분류할 수 있다. */
+        Err(IndexingError::EsUnavailable {
+            source: last_error.map(Box::new),
+        })
     }
 }
 
-#[doc = "Function to return Elasticsearch connection objects"]
-pub async fn release_elastic_conn(es_repo: EsRepositoryPub) {
-    let mut pool: MutexGuard<'_, VecDeque<EsRepositoryPub>> = ELASTICSEARCH_CONN_POOL.lock().await;
-
-    pool.push_back(es_repo);
-    info!(
-        "[connection return] Elasticsearch pool.len = {:?}",
-        pool.len()
-    );
-}
-
 #[doc = "Functions that return Elasticsearch guard connections"]
-pub async fn get_elastic_guard_conn() -> Result<ElasticConnGuard, anyhow::Error> {
-    let es_guard: ElasticConnGuard = ElasticConnGuard::new().await?;
+pub async fn get_elastic_guard_conn() -> Result<ElasticConnGuard, IndexingError> {
+    let es_guard: ElasticConnGuard = get_elastic_conn().await?;
 
     Ok(es_guard)
 }
 
 #[doc = "RAII Pattern: Guard to automatically return connections"]
+/// guard 가 drop 되는 즉시 permit 이 반환되므로 runtime 종료 중에도 connection 을 잃어버리지 않는다.
 pub struct ElasticConnGuard {
-    es_repo: Option<EsRepositoryPub>,
-}
-
-impl ElasticConnGuard {
-    pub async fn new() -> Result<Self, anyhow::Error> {
-        let es_repo: EsRepositoryPub = get_elastic_conn().await?;
-        Ok(Self {
-            es_repo: Some(es_repo),
-        })
-    }
+    pool: &'static EsConnPool,
+    permit: Option<OwnedSemaphorePermit>,
 }
 
 impl Deref for ElasticConnGuard {
     type Target = EsRepositoryPub;
     fn deref(&self) -> &Self::Target {
-        self.es_repo
-            .as_ref()
-            .expect("[Error] Attempted to dereference an empty ElasticConnGuard")
+        &self.pool.es_repo
     }
 }
 
 impl Drop for ElasticConnGuard {
     fn drop(&mut self) {
-        if let Some(es_repo) = self.es_repo.take() {
-            let _ = tokio::spawn(async move {
-                release_elastic_conn(es_repo).await;
-            });
+        /* permit 을 먼저 반환해야 유휴 connection 수가 정확하게 기록된다. */
+        drop(self.permit.take());
+        self.pool.record_pool_stats();
+    }
+}
+
+#[doc = "bulk 요청 본문에 들어갈 문서 한건 -> (action line, source line, payload 크기)"]
+type BulkDocument = (Vec<u8>, Vec<u8>, usize);
+
+#[doc = "문서를 bulk 요청 본문용으로 한번만 직렬화해주는 함수 -> 같은 바이트를 크기 계산과 요청 본문에 함께 사용한다."]
+/// # Arguments
+/// * `document` - 색인할 문서
+///
+/// # Returns
+/// * Result<BulkDocument, IndexingError>
+fn serialize_bulk_document<T: EsDocument>(document: &T) -> Result<BulkDocument, IndexingError> {
+    /* Same ID is overwritten */
+    let action_line: Vec<u8> =
+        serde_json::to_vec(&json!({ "index": { "_id": document.document_id() } }))?;
+    let source_line: Vec<u8> = serde_json::to_vec(document)?;
+
+    /* action line + source line, each followed by a newline */
+    let payload_size: usize = action_line.len() + source_line.len() + 2;
+
+    Ok((action_line, source_line, payload_size))
+}
+
+#[doc = "직렬화된 문서들을 문서 수와 payload 크기 제한에 맞춰 bulk 요청 단위로 나눠주는 함수"]
+/// # Arguments
+/// * `documents` - 직렬화된 문서 목록
+/// * `batch_size` - bulk 요청 하나에 들어갈 최대 문서 수
+/// * `max_bulk_bytes` - bulk 요청 하나의 최대 payload 크기 -> 이보다 큰 문서는 단독으로 요청된다.
+///
+/// # Returns
+/// * Vec<&[BulkDocument]>
+fn split_bulk_chunks(
+    documents: &[BulkDocument],
+    batch_size: usize,
+    max_bulk_bytes: Option<usize>,
+) -> Vec<&[BulkDocument]> {
+    let max_bulk_bytes: usize = max_bulk_bytes.unwrap_or(usize::MAX);
+
+    let mut chunks: Vec<&[BulkDocument]> = Vec::new();
+    let mut chunk_start: usize = 0;
+    let mut chunk_bytes: usize = 0;
+
+    for (idx, (_, _, payload_size)) in documents.iter().enumerate() {
+        let chunk_len: usize = idx - chunk_start;
+
+        if chunk_len > 0
+            && (chunk_len >= batch_size
+                || chunk_bytes.saturating_add(*payload_size) > max_bulk_bytes)
+        {
+            chunks.push(&documents[chunk_start..idx]);
+            chunk_start = idx;
+            chunk_bytes = 0;
         }
+
+        chunk_bytes = chunk_bytes.saturating_add(*payload_size);
     }
+
+    if chunk_start < documents.len() {
+        chunks.push(&documents[chunk_start..]);
+    }
+
+    chunks
 }
 
 #[async_trait]
@@ -285,21 +385,23 @@ impl EsRepository for EsRepositoryPub {
     /// * `response` - Query response json value.
     ///
     /// # Returns
-    /// * Result<(), anyhow::Error>
+    /// * Result<(), IndexingError>
     async fn process_response_empty(
         &self,
         function_name: &str,
         response: Response,
-    ) -> Result<(), anyhow::Error> {
+    ) -> Result<(), IndexingError> {
+        let status: u16 = response.status_code().as_u16();
+
         if response.status_code().is_success() {
             Ok(())
         } else {
             let error_body: String = response.text().await?;
-            Err(anyhow!(
-                "[Elasticsearch Error][{}] response status is failed: {:?}",
-                function_name,
-                error_body
-            ))
+            Err(IndexingError::EsStatus {
+                function_name: function_name.to_string(),
+                status,
+                error_body,
+            })
         }
     }
 
@@ -310,22 +412,24 @@ impl EsRepository for EsRepositoryPub {
     /// * `response` - Query response json value.
     ///
     /// # Returns
-    /// * Result<Value, anyhow::Error>
+    /// * Result<Value, IndexingError>
     async fn process_response(
         &self,
         function_name: &str,
         response: Response,
-    ) -> Result<Value, anyhow::Error> {
+    ) -> Result<Value, IndexingError> {
+        let status: u16 = response.status_code().as_u16();
+
         if response.status_code().is_success() {
             let response_body: Value = response.json::<Value>().await?;
             Ok(response_body)
         } else {
             let error_body: String = response.text().await?;
-            Err(anyhow!(
-                "[Elasticsearch Error][{}] response status is failed: {:?}",
-                function_name,
-                error_body
-            ))
+            Err(IndexingError::EsStatus {
+                function_name: function_name.to_string(),
+                status,
+                error_body,
+            })
         }
     }
 
@@ -336,13 +440,16 @@ impl EsRepository for EsRepositoryPub {
     /// * `old_index_name` - Index name mapped to alias
     ///
     /// # Returns
-    /// * Result<(), anyhow::Error>
+    /// * Result<(), IndexingError>
     async fn update_index_alias(
         &self,
         index_alias: &str,
         new_index_name: &str,
         old_index_name: &str,
-    ) -> Result<(), anyhow::Error> {
+    ) -> Result<(), IndexingError> {
+        let index_admin_timeout: Duration =
+            *self.node_pool.get_connection_config().index_admin_timeout();
+
         let response: Response = self
             .execute_on_any_node(|es_client| async move {
                 let actions: Value = json!({
@@ -357,6 +464,7 @@ impl EsRepository for EsRepositoryPub {
                     .indices()
                     .update_aliases()
                     .body(actions)
+                    .request_timeout(index_admin_timeout)
                     .send()
                     .await?;
 
@@ -374,12 +482,15 @@ impl EsRepository for EsRepositoryPub {
     /// * `index_name` - Index name to be newly mapped to alias
     ///
     /// # Returns
-    /// * Result<(), anyhow::Error>
+    /// * Result<(), IndexingError>
     async fn create_index_alias(
         &self,
         index_alias: &str,
         index_name: &str,
-    ) -> Result<(), anyhow::Error> {
+    ) -> Result<(), IndexingError> {
+        let index_admin_timeout: Duration =
+            *self.node_pool.get_connection_config().index_admin_timeout();
+
         let response: Response = self
             .execute_on_any_node(|es_client| async move {
                 let actions: Value = json!({
@@ -393,6 +504,7 @@ impl EsRepository for EsRepositoryPub {
                     .indices()
                     .update_aliases()
                     .body(actions)
+                    .request_timeout(index_admin_timeout)
                     .send()
                     .await?;
 
@@ -409,11 +521,11 @@ impl EsRepository for EsRepositoryPub {
     /// * `index_alias_name` - index alias name
     ///
     /// # Returns
-    /// * Result<Value, anyhow::Error>
+    /// * Result<Value, IndexingError>
     async fn get_indexes_mapping_by_alias(
         &self,
         index_alias_name: &str,
-    ) -> Result<Value, anyhow::Error> {
+    ) -> Result<Value, IndexingError> {
         let response: Response = self
             .execute_on_any_node(|es_client| async move {
                 let response: Response = es_client
@@ -437,12 +549,15 @@ impl EsRepository for EsRepositoryPub {
     /// * `index_setting_json` - setting/mapping information of Index
     ///
     /// # Returns
-    /// * Result<(), anyhow::Error>
+    /// * Result<(), IndexingError>
     async fn create_index(
         &self,
         index_name: &str,
         index_setting_json: &Value,
-    ) -> Result<(), anyhow::Error> {
+    ) -> Result<(), IndexingError> {
+        let index_admin_timeout: Duration =
+            *self.node_pool.get_connection_config().index_admin_timeout();
+
         let response: Response = self
             .execute_on_any_node(|es_client| async move {
                 let response: Response = es_client
@@ -450,6 +565,7 @@ impl EsRepository for EsRepositoryPub {
                     .indices()
                     .create(IndicesCreateParts::Index(index_name))
                     .body(index_setting_json)
+                    .request_timeout(index_admin_timeout)
                     .send()
                     .await?;
 
@@ -461,49 +577,146 @@ impl EsRepository for EsRepositoryPub {
             .await
     }
 
+    #[doc = "Function that processes the response of a bulk request.
+    Elasticsearch returns 200 even if individual items fail, so the items of the body are inspected."]
+    /// # Arguments
+    /// * `function_name` - Name of the function that makes a bulk request to Elasticsearch.
+    /// * `response` - Bulk query response.
+    ///
+    /// # Returns
+    /// * Result<Vec<BulkItemFailure>, IndexingError> - Items that failed to be indexed
+    async fn process_bulk_response(
+        &self,
+        function_name: &str,
+        response: Response,
+    ) -> Result<Vec<BulkItemFailure>, IndexingError> {
+        let response_body: Value = self.process_response(function_name, response).await?;
+
+        let mut failures: Vec<BulkItemFailure> = Vec::new();
+
+        if !response_body["errors"].as_bool().unwrap_or(false) {
+            return Ok(failures);
+        }
+
+        let items: &Vec<Value> =
+            response_body["items"]
+                .as_array()
+                .ok_or_else(|| IndexingError::EsInvalidResponse {
+                    function_name: function_name.to_string(),
+                    reason: "Failed to parse 'items' of bulk response".to_string(),
+                })?;
+
+        for item in items {
+            /* { "index": { "_id": .., "status": .., "error": { "type": .., "reason": .. } } } */
+            let item_result: &Value = match item.as_object().and_then(|map| map.values().next()) {
+                Some(item_result) => item_result,
+                None => continue,
+            };
+
+            if item_result.get("error").is_none() {
+                continue;
+            }
+
+            let status: u16 = item_result["status"].as_u64().unwrap_or_default() as u16;
+
+            failures.push(BulkItemFailure::new(
+                item_result["_id"].as_str().unwrap_or_default().to_string(),
+                status,
+                item_result["error"]["type"]
+                    .as_str()
+                    .unwrap_or_default()
+                    .to_string(),
+                item_result["error"]["reason"]
+                    .as_str()
+                    .unwrap_or_default()
+                    .to_string(),
+                BulkItemFailure::is_retryable_status(status),
+            ));
+        }
+
+        Ok(failures)
+    }
+
     #[doc = "Function to index data to Elasticsearch at once"]
     /// # Arguments
     /// * `index_name` - index name
     /// * `data` - Data vectors to be indexed
-    /// * `batch_size` - Number of documents to perform bulk indexing operations at a time
+    /// * `batch_size` - Maximum number of documents in a single bulk request
+    /// * `max_bulk_bytes` - Maximum payload size of a single bulk request (None means unlimited)
     ///
     /// # Returns
-    /// * Result<(), anyhow::Error>
-    async fn bulk_indexing_query<T: Serialize + Send + Sync>(
+    /// * Result<Vec<BulkItemFailure>, IndexingError> - Items that failed to be indexed
+    async fn bulk_indexing_query<T: EsDocument>(
         &self,
         index_name: &str,
         data: &[T],
         batch_size: usize,
-    ) -> Result<(), anyhow::Error> {
-        for chunk in data.chunks(batch_size) {
-            let response: Response = self
-                .execute_on_any_node(|es_client| async move {
-                    let mut ops: Vec<BulkOperation<Value>> = Vec::with_capacity(chunk.len());
+        max_bulk_bytes: Option<usize>,
+    ) -> Result<Vec<BulkItemFailure>, IndexingError> {
+        let mut failures: Vec<BulkItemFailure> = Vec::new();
 
-                    for item in chunk {
-                        /* Converting Data to JSON */
-                        let json_value: Value = serde_json::to_value(item)?;
+        /* Converting Data to JSON -> 문서마다 한번만 직렬화한다. */
+        let documents: Vec<BulkDocument> = data
+            .iter()
+            .map(serialize_bulk_document)
+            .collect::<Result<_, _>>()?;
 
-                        /* BulkOperation Generation (without ID) */
-                        ops.push(BulkOperation::index(json_value).into());
-                    }
+        let bulk_timeout: Duration = *self.node_pool.get_connection_config().bulk_timeout();
+
+        for chunk in split_bulk_chunks(&documents, batch_size, max_bulk_bytes) {
+            let started_at: Instant = Instant::now();
+
+            let bulk_result: Result<Response, IndexingError> = self
+                .execute_on_any_node(|es_client| async move {
+                    let ops: Vec<&[u8]> = chunk
+                        .iter()
+                        .flat_map(|(action_line, source_line, _)| {
+                            [action_line.as_slice(), source_line.as_slice()]
+                        })
+                        .collect();
 
                     let response: Response = es_client
                         .es_conn
                         .bulk(BulkParts::Index(index_name))
                         .body(ops)
+                        .request_timeout(bulk_timeout)
                         .send()
                         .await?;
 
                     Ok(response)
                 })
-                .await?;
+                .await;
+
+            let chunk_result: Result<Vec<BulkItemFailure>, IndexingError> = match bulk_result {
+                Ok(response) => self.process_bulk_response("bulk_query()", response).await,
+                Err(e) => Err(e),
+            };
+
+            let mut chunk_failures: Vec<BulkItemFailure> = match chunk_result {
+                Ok(chunk_failures) => {
+                    get_indexing_metrics().record_bulk_request(
+                        index_name,
+                        started_at.elapsed(),
+                        false,
+                        chunk_failures.len(),
+                    );
+                    chunk_failures
+                }
+                Err(e) => {
+                    get_indexing_metrics().record_bulk_request(
+                        index_name,
+                        started_at.elapsed(),
+                        true,
+                        0,
+                    );
+                    return Err(e);
+                }
+            };
 
-            self.process_response_empty("bulk_query()", response)
-                .await?
+            failures.append(&mut chunk_failures);
         }
 
-        Ok(())
+        Ok(failures)
     }
 
     #[doc = "function that deletes the id in the final step of scroll-api"]
@@ -511,8 +724,8 @@ impl EsRepository for EsRepositoryPub {
     /// * `scroll_id` - scroll api ID
     ///
     /// # Returns
-    /// * Result<(), anyhow::Error>
-    async fn clear_scroll_info(&self, scroll_id: &str) -> Result<(), anyhow::Error> {
+    /// * Result<(), IndexingError>
+    async fn clear_scroll_info(&self, scroll_id: &str) -> Result<(), IndexingError> {
         let response: Response = self
             .execute_on_any_node(|es_client| async move {
                 let response = es_client
@@ -535,12 +748,12 @@ impl EsRepository for EsRepositoryPub {
     /// * `scroll_id` - scroll api ID
     ///
     /// # Returns
-    /// * Result<Value, anyhow::Error>
+    /// * Result<Value, IndexingError>
     async fn get_scroll_search_query(
         &self,
         scroll_duration: &str,
         scroll_id: &str,
-    ) -> Result<Value, anyhow::Error> {
+    ) -> Result<Value, IndexingError> {
         let response: Response = self
             .execute_on_any_node(|es_client| async move {
                 let scroll_response = es_client
@@ -565,13 +778,13 @@ impl EsRepository for EsRepositoryPub {
     /// * `es_query` - Query format
     ///
     /// # Returns
-    /// * Result<Value, anyhow::Error>
+    /// * Result<Value, IndexingError>
     async fn get_scroll_initial_search_query(
         &self,
         index_name: &str,
         scroll_duration: &str,
         es_query: &Value,
-    ) -> Result<Value, anyhow::Error> {
+    ) -> Result<Value, IndexingError> {
         let response: Response = self
             .execute_on_any_node(|es_client| async move {
                 let response = es_client
@@ -596,12 +809,12 @@ impl EsRepository for EsRepositoryPub {
     /// * `index_name` - Name of Elasticsearch index
     ///
     /// # Returns
-    /// * Result<Value, anyhow::Error>
+    /// * Result<Value, IndexingError>
     async fn get_search_query(
         &self,
         es_query: &Value,
         index_name: &str,
-    ) -> Result<Value, anyhow::Error> {
+    ) -> Result<Value, IndexingError> {
         let response: Response = self
             .execute_on_any_node(|es_client| async move {
                 let response = es_client
@@ -618,32 +831,14 @@ impl EsRepository for EsRepositoryPub {
         self.process_response("get_search_query()", response).await
     }
 
-    #[doc = "Function that EXECUTES elasticsearch queries - indexing struct"]
-    /// # Arguments
-    /// * `param_struct` - Structural Forms to Index
-    /// * `index_name` - Name of Elasticsearch index
-    ///
-    /// # Returns
-    /// * Result<(), anyhow::Error>
-    async fn post_query_struct<T: Serialize + Sync>(
-        &self,
-        param_struct: &T,
-        index_name: &str,
-    ) -> Result<(), anyhow::Error> {
-        let struct_json: Value = convert_json_from_struct(param_struct)?;
-        self.post_query(&struct_json, index_name).await?;
-
-        Ok(())
-    }
-
     #[doc = "Function that EXECUTES elasticsearch queries - indexing"]
     /// # Arguments
     /// * `document` - Json data to index
     /// * `index_name` - Name of Elasticsearch index
     ///
     /// # Returns
-    /// * Result<(), anyhow::Error>
-    async fn post_query(&self, document: &Value, index_name: &str) -> Result<(), anyhow::Error> {
+    /// * Result<(), IndexingError>
+    async fn post_query(&self, document: &Value, index_name: &str) -> Result<(), IndexingError> {
         let response: Response = self
             .execute_on_any_node(|es_client| async move {
                 let response = es_client
@@ -665,14 +860,18 @@ impl EsRepository for EsRepositoryPub {
     /// * `index_name` - Index name to be deleted
     ///
     /// # Returns
-    /// * Result<(), anyhow::Error>
-    async fn delete_query(&self, index_name: &str) -> Result<(), anyhow::Error> {
+    /// * Result<(), IndexingError>
+    async fn delete_query(&self, index_name: &str) -> Result<(), IndexingError> {
+        let index_admin_timeout: Duration =
+            *self.node_pool.get_connection_config().index_admin_timeout();
+
         let response: Response = self
             .execute_on_any_node(|es_client| async move {
                 let response = es_client
                     .es_conn
                     .indices()
                     .delete(IndicesDeleteParts::Index(&[index_name]))
+                    .request_timeout(index_admin_timeout)
                     .send()
                     .await?;
 
@@ -690,8 +889,8 @@ impl EsRepository for EsRepositoryPub {
     /// * `index_name` - Index name to be deleted
     ///
     /// # Returns
-    /// * Result<(), anyhow::Error>
-    async fn delete_query_doc(&self, doc_id: &str, index_name: &str) -> Result<(), anyhow::Error> {
+    /// * Result<(), IndexingError>
+    async fn delete_query_doc(&self, doc_id: &str, index_name: &str) -> Result<(), IndexingError> {
         let response: Response = self
             .execute_on_any_node(|es_client| async move {
                 let response = es_client
@@ -708,20 +907,22 @@ impl EsRepository for EsRepositoryPub {
             .await
     }
 
-    #[doc = "Function that EXECUTES elasticsearch queries - delete by field"]
+    #[doc = "Function that EXECUTES elasticsearch queries - delete by multiple field values"]
     /// # Arguments
     /// * `index_name` - Index name to be deleted
     /// * `field_name` - Field name to be deleted
-    /// * `field_value` - Field value to be deleted
+    /// * `field_values` - Field values to be deleted
     ///
     /// # Returns
-    /// * Result<(), anyhow::Error>
-    async fn delete_query_where_field(
+    /// * Result<(), IndexingError>
+    async fn delete_query_where_field_in(
         &self,
         index_name: &str,
         field_name: &str,
-        field_value: i32,
-    ) -> Result<(), anyhow::Error> {
+        field_values: &[i32],
+    ) -> Result<(), IndexingError> {
+        let bulk_timeout: Duration = *self.node_pool.get_connection_config().bulk_timeout();
+
         let response: Response = self
             .execute_on_any_node(|es_client| async move {
                 let response = es_client
@@ -729,11 +930,12 @@ impl EsRepository for EsRepositoryPub {
                     .delete_by_query(DeleteByQueryParts::Index(&[index_name]))
                     .body(json!({
                         "query": {
-                            "term": {
-                                field_name: field_value
+                            "terms": {
+                                field_name: field_values
                             }
                         }
                     }))
+                    .request_timeout(bulk_timeout)
                     .send()
                     .await?;
 
@@ -741,7 +943,7 @@ impl EsRepository for EsRepositoryPub {
             })
             .await?;
 
-        self.process_response_empty("refresh_index()", response)
+        self.process_response_empty("delete_query_where_field_in()", response)
             .await
     }
 
@@ -750,14 +952,18 @@ impl EsRepository for EsRepositoryPub {
     /// * `index_name` - Index name to be refresh
     ///
     /// # Returns
-    /// * Result<(), anyhow::Error>
-    async fn refresh_index(&self, index_name: &str) -> Result<(), anyhow::Error> {
+    /// * Result<(), IndexingError>
+    async fn refresh_index(&self, index_name: &str) -> Result<(), IndexingError> {
+        let index_admin_timeout: Duration =
+            *self.node_pool.get_connection_config().index_admin_timeout();
+
         let response: Response = self
             .execute_on_any_node(|es_client| async move {
                 let response = es_client
                     .es_conn
                     .indices()
                     .refresh(IndicesRefreshParts::Index(&[index_name]))
+                    .request_timeout(index_admin_timeout)
                     .send()
                     .await?;
 
@@ -774,8 +980,8 @@ impl EsRepository for EsRepositoryPub {
     /// * `index_name` - Index name to be find
     ///
     /// # Returns
-    /// * Result<(), anyhow::Error>
-    async fn check_index_exist(&self, index_name: &str) -> Result<Value, anyhow::Error> {
+    /// * Result<(), IndexingError>
+    async fn check_index_exist(&self, index_name: &str) -> Result<Value, IndexingError> {
         let response: Response = self
             .execute_on_any_node(|es_client| async move {
                 let response: Response = es_client
@@ -791,4 +997,58 @@ impl EsRepository for EsRepositoryPub {
 
         self.process_response("check_index_exist()", response).await
     }
+
+    #[doc = "Function that returns the number of documents in an index"]
+    /// # Arguments
+    /// * `index_name` - Index name to be counted
+    ///
+    /// # Returns
+    /// * Result<u64, IndexingError>
+    async fn get_index_count(&self, index_name: &str) -> Result<u64, IndexingError> {
+        let response: Response = self
+            .execute_on_any_node(|es_client| async move {
+                let response: Response = es_client
+                    .es_conn
+                    .count(CountParts::Index(&[index_name]))
+                    .send()
+                    .await?;
+
+                Ok(response)
+            })
+            .await?;
+
+        let response_body: Value = self.process_response("get_index_count()", response).await?;
+
+        response_body["count"]
+            .as_u64()
+            .ok_or_else(|| IndexingError::EsInvalidResponse {
+                function_name: "get_index_count()".to_string(),
+                reason: "Failed to parse 'count'".to_string(),
+            })
+    }
+
+    #[doc = "Function that returns the name, document count and creation date of indices matching a pattern"]
+    /// # Arguments
+    /// * `index_pattern` - Index name pattern (wildcards allowed)
+    ///
+    /// # Returns
+    /// * Result<Value, IndexingError>
+    async fn get_cat_indices(&self, index_pattern: &str) -> Result<Value, IndexingError> {
+        let response: Response = self
+            .execute_on_any_node(|es_client| async move {
+                let response: Response = es_client
+                    .es_conn
+                    .cat()
+                    .indices(CatIndicesParts::Index(&[index_pattern]))
+                    .format("json")
+                    .h(&["index", "docs.count", "creation.date.string"])
+                    .send()
+                    .await?;
+
+                Ok(response)
+            })
+            .await?;
+
+        self.process_response("get_cat_indices()", response).await
+    }
 }
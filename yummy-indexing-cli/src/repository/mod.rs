@@ -1,2 +1,4 @@
+pub mod es_node_pool;
 pub mod es_repository;
 pub mod mysql_repository;
+pub mod telegram_repository;
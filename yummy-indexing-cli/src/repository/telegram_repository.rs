@@ -0,0 +1,126 @@
+use crate::common::*;
+
+use crate::configuration::index_schedules_config::*;
+
+#[doc = "Telegram bot client to be used in a single tone -> None if the bot token is not set"]
+static TELEGRAM_CLIENT: std::sync::OnceLock<Option<TelegramRepositoryPub>> =
+    std::sync::OnceLock::new();
+
+#[doc = "bot token 을 읽을 환경변수 이름의 기본값"]
+const DEFAULT_BOT_TOKEN_ENV: &str = "TELEGRAM_BOT_TOKEN";
+
+#[doc = "Function to initialize the Telegram bot client -> 알림 설정을 읽은 뒤 한번 호출해야 한다."]
+/// bot token 은 설정 파일에 직접 적지 않고 `bot_token_env` 에 지정된 환경변수에서 읽는다.
+/// `chat_ids` 가 설정 파일에 없으면 'TELEGRAM_CHAT_IDS' 환경변수를 사용한다.
+///
+/// # Arguments
+/// * `notification_config` - 알림 설정
+pub fn initialize_telegram_client(notification_config: &NotificationConfig) {
+    TELEGRAM_CLIENT.get_or_init(|| create_telegram_client(notification_config));
+}
+
+#[doc = "알림 설정으로 Telegram bot client 를 만들어주는 함수"]
+/// # Arguments
+/// * `notification_config` - 알림 설정
+///
+/// # Returns
+/// * Option<TelegramRepositoryPub> - bot token 이나 chat id 가 없으면 None
+fn create_telegram_client(
+    notification_config: &NotificationConfig,
+) -> Option<TelegramRepositoryPub> {
+    dotenv().ok();
+
+    let bot_token_env: &str = notification_config
+        .bot_token_env()
+        .as_deref()
+        .unwrap_or(DEFAULT_BOT_TOKEN_ENV);
+
+    /* 알림은 선택 기능이므로 bot token 이 없으면 알림을 보내지 않는다. */
+    let bot_token: String = match env::var(bot_token_env) {
+        Ok(bot_token) if !bot_token.trim().is_empty() => bot_token,
+        _ => {
+            info!("[initialize_telegram_client()] '{}' is not set. Telegram notifications are disabled.", bot_token_env);
+            return None;
+        }
+    };
+
+    let chat_ids: Vec<ChatId> = if !notification_config.chat_ids().is_empty() {
+        notification_config
+            .chat_ids()
+            .iter()
+            .map(|chat_id| ChatId(*chat_id))
+            .collect()
+    } else {
+        match env::var("TELEGRAM_CHAT_IDS") {
+            Ok(chat_ids) => {
+                let parsed_ids: Result<Vec<ChatId>, _> = chat_ids
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|chat_id| !chat_id.is_empty())
+                    .map(|chat_id| chat_id.parse::<i64>().map(ChatId))
+                    .collect();
+
+                match parsed_ids {
+                    Ok(parsed_ids) => parsed_ids,
+                    Err(e) => {
+                        error!("[Error][initialize_telegram_client()] Invalid 'TELEGRAM_CHAT_IDS': {:?}. Telegram notifications are disabled.", e);
+                        return None;
+                    }
+                }
+            }
+            Err(_) => Vec::new(),
+        }
+    };
+
+    if chat_ids.is_empty() {
+        warn!("[initialize_telegram_client()] 'notification.chat_ids' and 'TELEGRAM_CHAT_IDS' are empty. Telegram notifications are disabled.");
+        return None;
+    }
+
+    Some(TelegramRepositoryPub::new(Bot::new(bot_token), chat_ids))
+}
+
+#[doc = "Function to get the Telegram bot client"]
+pub fn get_telegram_client() -> Option<&'static TelegramRepositoryPub> {
+    TELEGRAM_CLIENT.get().and_then(Option::as_ref)
+}
+
+#[async_trait]
+pub trait TelegramRepository {
+    async fn send_message(&self, message: &str) -> Result<(), anyhow::Error>;
+}
+
+#[derive(Debug, Clone, new)]
+pub struct TelegramRepositoryPub {
+    bot: Bot,
+    chat_ids: Vec<ChatId>,
+}
+
+#[async_trait]
+impl TelegramRepository for TelegramRepositoryPub {
+    #[doc = "Function that sends a message to every configured chat"]
+    /// # Arguments
+    /// * `message` - Message text
+    ///
+    /// # Returns
+    /// * Result<(), anyhow::Error>
+    async fn send_message(&self, message: &str) -> Result<(), anyhow::Error> {
+        let mut failed_chats: Vec<String> = Vec::new();
+
+        /* 한 채팅방으로의 전송이 실패해도 나머지 채팅방에는 보내준다. */
+        for chat_id in &self.chat_ids {
+            if let Err(e) = self.bot.send_message(*chat_id, message).await {
+                failed_chats.push(format!("{}: {}", chat_id, e));
+            }
+        }
+
+        if !failed_chats.is_empty() {
+            return Err(anyhow!(
+                "[Telegram Error][send_message()] Failed to send the message.: {}",
+                failed_chats.join(", ")
+            ));
+        }
+
+        Ok(())
+    }
+}
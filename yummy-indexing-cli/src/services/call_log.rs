@@ -0,0 +1,45 @@
+use crate::common::*;
+
+#[doc = "테스트용 서비스들이 호출된 순서를 함께 기록하는 로그 -> 서비스 사이의 호출 순서를 검증할 때 사용한다."]
+#[derive(Debug, Clone, Default)]
+pub struct CallLog {
+    calls: Arc<std::sync::Mutex<Vec<String>>>,
+}
+
+impl CallLog {
+    #[doc = "호출을 기록해주는 함수"]
+    /// # Arguments
+    /// * `call` - 호출된 함수 이름
+    pub fn record(&self, call: &str) {
+        self.calls
+            .lock()
+            .expect("[Error][CallLog::record()] call log is poisoned")
+            .push(call.to_string());
+    }
+
+    #[doc = "지금까지 기록된 호출들을 가져와주는 함수"]
+    pub fn calls(&self) -> Vec<String> {
+        self.calls
+            .lock()
+            .expect("[Error][CallLog::calls()] call log is poisoned")
+            .clone()
+    }
+
+    #[doc = "해당 함수가 처음 호출된 위치를 가져와주는 함수"]
+    /// # Arguments
+    /// * `call` - 함수 이름
+    ///
+    /// # Returns
+    /// * Option<usize> - 호출되지 않았으면 None
+    pub fn position(&self, call: &str) -> Option<usize> {
+        self.calls().iter().position(|recorded| recorded == call)
+    }
+
+    #[doc = "해당 함수가 호출된 횟수를 가져와주는 함수"]
+    pub fn count(&self, call: &str) -> usize {
+        self.calls()
+            .iter()
+            .filter(|recorded| *recorded == call)
+            .count()
+    }
+}
@@ -2,23 +2,35 @@ use crate::common::*;
 
 use crate::configuration::index_schedules_config::*;
 
+use crate::models::bulk_result::*;
+use crate::models::es_document::*;
+use crate::models::index_generation::*;
+use crate::models::indexing_error::*;
+
 use crate::repository::es_repository::*;
 
 use crate::utils_module::io_utils::*;
 use crate::utils_module::time_utils::*;
 
 #[async_trait]
-pub trait EsQueryService {
-    async fn post_indexing_data_by_bulk_static<T: Serialize + Send + Sync + Debug>(
+pub trait EsQueryService: Send + Sync {
+    async fn create_static_index(
         &self,
         index_schedule: &IndexSchedules,
-        data: &[T],
-    ) -> Result<(), anyhow::Error>;
+    ) -> Result<String, anyhow::Error>;
 
-    async fn post_indexing_data_by_bulk_dynamic<T: Serialize + Send + Sync + Debug>(
+    async fn post_indexing_data_by_bulk<T: EsDocument + Debug>(
         &self,
         index_schedule: &IndexSchedules,
+        index_name: &str,
         data: &[T],
+    ) -> Result<usize, anyhow::Error>;
+
+    async fn swap_static_index_alias(
+        &self,
+        index_schedule: &IndexSchedules,
+        new_index_name: &str,
+        source_cnt: usize,
     ) -> Result<(), anyhow::Error>;
 
     async fn get_recent_index_datetime(
@@ -27,52 +39,383 @@ pub trait EsQueryService {
         timestamp_field: &str,
     ) -> Result<NaiveDateTime, anyhow::Error>;
 
-    async fn update_index<T: Serialize + Send + Sync + Debug>(
+    async fn delete_index_by_field_values(
         &self,
         index_schedule: &IndexSchedules,
-        data: &[T],
+        field_values: &[i32],
         unique_field_name: &str,
     ) -> Result<(), anyhow::Error>;
 
-    async fn delete_index<T: Serialize + Send + Sync + Debug>(
+    async fn get_index_generations(
         &self,
-        index_schedule: &IndexSchedules,
-        data: &[T],
-        unique_field_name: &str,
+        index_alias_name: &str,
+    ) -> Result<Vec<IndexGeneration>, anyhow::Error>;
+
+    async fn rollback_index_alias(
+        &self,
+        index_alias_name: &str,
+        target_index_name: &str,
+    ) -> Result<(), anyhow::Error>;
+
+    async fn delete_index_generation(
+        &self,
+        index_alias_name: &str,
+        index_name: &str,
     ) -> Result<(), anyhow::Error>;
 
+    async fn get_index_doc_count(&self, index_name: &str) -> Result<u64, anyhow::Error>;
+
     async fn get_test(&self) -> Result<(), anyhow::Error>;
 }
 
 #[derive(Debug, new)]
 pub struct EsQueryServicePub;
 
-#[async_trait]
-impl EsQueryService for EsQueryServicePub {
-    #[doc = "Elasticsearch - static index function"]
+#[doc = "최종적으로 색인에 실패한 문서들을 기록하는 디렉토리"]
+const DEAD_LETTER_DIRECTORY: &str = "dead_letter";
+
+impl EsQueryServicePub {
+    #[doc = "connection 을 하나 받아서 bulk 요청을 한번 보내주는 함수 -> 요청이 끝나면 바로 connection 을 반납한다."]
     /// # Arguments
     /// * `index_schedule` - Index schedule information
+    /// * `index_name` - Name of the index to be indexed
     /// * `data` - Vector information to be indexed
     ///
     /// # Returns
-    /// * Result<(), anyhow::Error>
-    async fn post_indexing_data_by_bulk_static<T: Serialize + Send + Sync + Debug>(
+    /// * Result<Vec<BulkItemFailure>, anyhow::Error> - Items that failed to be indexed
+    async fn bulk_indexing_once<T: EsDocument>(
         &self,
         index_schedule: &IndexSchedules,
+        index_name: &str,
         data: &[T],
+    ) -> Result<Vec<BulkItemFailure>, anyhow::Error> {
+        let es_conn: ElasticConnGuard = get_elastic_guard_conn().await?;
+
+        let failures: Vec<BulkItemFailure> = es_conn
+            .bulk_indexing_query(
+                index_name,
+                data,
+                *index_schedule.es_batch_size(),
+                *index_schedule.es_max_bulk_bytes(),
+            )
+            .await?;
+
+        Ok(failures)
+    }
+
+    #[doc = "bulk 색인 후 실패한 문서를 재시도하고, 끝내 실패한 문서는 dead-letter 파일로 남겨주는 함수"]
+    /// # Arguments
+    /// * `index_schedule` - Index schedule information
+    /// * `index_name` - Name of the index to be indexed
+    /// * `data` - Vector information to be indexed
+    ///
+    /// # Returns
+    /// * Result<usize, anyhow::Error> - 재시도 후에도 색인에 실패한 문서 수 -> 실패 여부는 호출하는 쪽에서 check_bulk_failure_ratio() 로 판단한다.
+    async fn bulk_indexing_with_retry<T: EsDocument + Debug>(
+        &self,
+        index_schedule: &IndexSchedules,
+        index_name: &str,
+        data: &[T],
+    ) -> Result<usize, anyhow::Error> {
+        let mut failures: Vec<BulkItemFailure> = self
+            .bulk_indexing_once(index_schedule, index_name, data)
+            .await?;
+
+        if failures.is_empty() {
+            return Ok(0);
+        }
+
+        let document_map: HashMap<String, &T> = data
+            .iter()
+            .map(|document| (document.document_id(), document))
+            .collect();
+
+        let mut permanent_failures: Vec<BulkItemFailure> = Vec::new();
+        let mut retry_delay_ms: u64 = *index_schedule.bulk_retry_delay_ms();
+
+        for attempt in 1..=*index_schedule.bulk_retry_count() {
+            let (retryable, mut permanent): (Vec<BulkItemFailure>, Vec<BulkItemFailure>) =
+                failures.into_iter().partition(|failure| failure.retryable);
+
+            permanent_failures.append(&mut permanent);
+            failures = retryable;
+
+            if failures.is_empty() {
+                break;
+            }
+
+            warn!(
+                "[bulk_indexing_with_retry()] Retry {} failed documents of '{}' after {}ms. (attempt {})",
+                failures.len(),
+                index_name,
+                retry_delay_ms,
+                attempt
+            );
+
+            /* 대기하는 동안에는 connection 을 잡고 있지 않도록 재시도마다 새로 받아온다. */
+            tokio::time::sleep(Duration::from_millis(retry_delay_ms)).await;
+            retry_delay_ms *= 2;
+
+            let retry_documents: Vec<&T> = failures
+                .iter()
+                .filter_map(|failure| document_map.get(&failure.document_id).copied())
+                .collect();
+
+            failures = self
+                .bulk_indexing_once(index_schedule, index_name, &retry_documents)
+                .await?;
+        }
+
+        /* 재시도 횟수를 모두 소진한 실패건도 최종 실패로 본다. */
+        permanent_failures.append(&mut failures);
+
+        if permanent_failures.is_empty() {
+            return Ok(0);
+        }
+
+        let dead_letter_path: String = format!(
+            "{}/{}_{}.ndjson",
+            DEAD_LETTER_DIRECTORY,
+            index_name,
+            get_current_utc_naive_datetime().format("%Y%m%d%H%M%S")
+        );
+
+        let dead_letters: Vec<Value> = permanent_failures
+            .iter()
+            .map(|failure| {
+                json!({
+                    "index": index_name,
+                    "document_id": failure.document_id,
+                    "status": failure.status,
+                    "error_type": failure.error_type,
+                    "reason": failure.reason,
+                    "source": document_map
+                        .get(&failure.document_id)
+                        .and_then(|document| serde_json::to_value(document).ok())
+                })
+            })
+            .collect();
+
+        append_ndjson_to_file(&dead_letter_path, &dead_letters)?;
+
+        error!(
+            "[bulk_indexing_with_retry()] {} of {} documents failed to be indexed in '{}'. dead-letter: {}",
+            permanent_failures.len(),
+            data.len(),
+            index_name,
+            dead_letter_path
+        );
+
+        Ok(permanent_failures.len())
+    }
+
+    #[doc = "정적색인으로 새로 만든 인덱스의 문서 수를 기존 인덱스, 원본 데이터 수와 비교하여 검증해주는 함수"]
+    /// # Arguments
+    /// * `es_conn` - Elasticsearch connection
+    /// * `index_schedule` - Index schedule information
+    /// * `new_index_name` - Name of the newly created index
+    /// * `old_index_name` - Name of the index currently mapped to the alias
+    /// * `source_cnt` - Number of source rows read from MySQL
+    ///
+    /// # Returns
+    /// * Result<(), anyhow::Error>
+    async fn validate_new_index(
+        &self,
+        es_conn: &EsRepositoryPub,
+        index_schedule: &IndexSchedules,
+        new_index_name: &str,
+        old_index_name: Option<&str>,
+        source_cnt: usize,
     ) -> Result<(), anyhow::Error> {
+        /* 색인된 문서가 count 에 반영되도록 refresh 를 먼저 해준다. */
+        es_conn.refresh_index(new_index_name).await?;
+
+        let new_cnt: u64 = es_conn.get_index_count(new_index_name).await?;
+
+        if let Some(min_doc_count) = index_schedule.min_doc_count() {
+            if new_cnt < *min_doc_count {
+                return Err(IndexingError::Validation {
+                    index_name: new_index_name.to_string(),
+                    reason: format!(
+                        "has fewer documents than 'min_doc_count'.: {} < {}",
+                        new_cnt, min_doc_count
+                    ),
+                }
+                .into());
+            }
+        }
+
+        let max_drop_percentage: f64 = match index_schedule.max_drop_percentage() {
+            Some(max_drop_percentage) => *max_drop_percentage,
+            None => return Ok(()),
+        };
+
+        let mut compare_targets: Vec<(String, u64)> =
+            vec![("source rows".to_string(), source_cnt as u64)];
+
+        if let Some(old_index_name) = old_index_name {
+            let old_cnt: u64 = es_conn.get_index_count(old_index_name).await?;
+            compare_targets.push((old_index_name.to_string(), old_cnt));
+        }
+
+        for (target_name, target_cnt) in compare_targets {
+            if target_cnt == 0 || new_cnt >= target_cnt {
+                continue;
+            }
+
+            let drop_percentage: f64 = (target_cnt - new_cnt) as f64 / target_cnt as f64 * 100.0;
+
+            if drop_percentage > max_drop_percentage {
+                return Err(IndexingError::Validation {
+                    index_name: new_index_name.to_string(),
+                    reason: format!(
+                        "dropped {:.2}% of documents compared to {}.: {} -> {} (max_drop_percentage: {})",
+                        drop_percentage, target_name, target_cnt, new_cnt, max_drop_percentage
+                    ),
+                }
+                .into());
+            }
+        }
+
+        info!(
+            "[validate_new_index()] '{}' passed validation. document count: {}",
+            new_index_name, new_cnt
+        );
+
+        Ok(())
+    }
+
+    #[doc = "alias 에 해당하는 정적색인 인덱스 세대들을 최신순으로 가져와주는 함수"]
+    /// # Arguments
+    /// * `es_conn` - Elasticsearch connection
+    /// * `index_alias_name` - index alias name
+    ///
+    /// # Returns
+    /// * Result<Vec<IndexGeneration>, anyhow::Error>
+    async fn fetch_index_generations(
+        &self,
+        es_conn: &EsRepositoryPub,
+        index_alias_name: &str,
+    ) -> Result<Vec<IndexGeneration>, anyhow::Error> {
+        let generation_regex: Regex =
+            Regex::new(&format!(r"^{}-\d{{14}}$", regex::escape(index_alias_name)))?;
+
+        /* alias 가 아직 존재하지 않는 경우(404)에만 맵핑된 인덱스가 없는 것으로 본다. */
+        let alias_targets: HashSet<String> =
+            match es_conn.get_indexes_mapping_by_alias(index_alias_name).await {
+                Ok(alias_resp) => alias_resp
+                    .as_object()
+                    .map(|map| map.keys().cloned().collect())
+                    .unwrap_or_default(),
+                Err(e) if e.is_not_found() => {
+                    warn!(
+                        "[fetch_index_generations()] No index is mapped to '{}'.: {:?}",
+                        index_alias_name, e
+                    );
+                    HashSet::new()
+                }
+                Err(e) => return Err(e.into()),
+            };
+
+        let cat_indices: Value = es_conn
+            .get_cat_indices(&format!("{}-*", index_alias_name))
+            .await?;
+
+        let mut generations: Vec<IndexGeneration> = cat_indices
+            .as_array()
+            .ok_or_else(|| {
+                anyhow!("[Error][fetch_index_generations()] Failed to parse 'cat indices' response")
+            })?
+            .iter()
+            .filter_map(|index_info| {
+                let index_name: &str = index_info["index"].as_str()?;
+
+                if !generation_regex.is_match(index_name) {
+                    return None;
+                }
+
+                Some(IndexGeneration::new(
+                    index_name.to_string(),
+                    index_info["docs.count"]
+                        .as_str()
+                        .and_then(|doc_count| doc_count.parse::<u64>().ok())
+                        .unwrap_or_default(),
+                    index_info["creation.date.string"]
+                        .as_str()
+                        .unwrap_or_default()
+                        .to_string(),
+                    alias_targets.contains(index_name),
+                ))
+            })
+            .collect();
+
+        /* 인덱스 이름 뒤의 타임스탬프 기준 최신순 정렬 */
+        generations.sort_by(|a, b| b.index_name.cmp(&a.index_name));
+
+        Ok(generations)
+    }
+
+    #[doc = "보관 세대 수(retention_count)를 넘어서는 오래된 인덱스 세대를 삭제해주는 함수"]
+    /// # Arguments
+    /// * `es_conn` - Elasticsearch connection
+    /// * `index_schedule` - Index schedule information
+    ///
+    /// # Returns
+    /// * Result<(), anyhow::Error>
+    async fn cleanup_index_generations(
+        &self,
+        es_conn: &EsRepositoryPub,
+        index_schedule: &IndexSchedules,
+    ) -> Result<(), anyhow::Error> {
+        let index_alias_name: &String = index_schedule.index_name();
+        let retention_count: usize = *index_schedule.retention_count();
+
+        let generations: Vec<IndexGeneration> = self
+            .fetch_index_generations(es_conn, index_alias_name)
+            .await?;
+
+        /* alias 에 맵핑된 인덱스는 보관 세대 수와 관계없이 절대 삭제하지 않는다. */
+        for generation in generations.iter().skip(retention_count) {
+            if generation.is_alias_target {
+                continue;
+            }
+
+            es_conn.delete_query(&generation.index_name).await?;
+            info!(
+                "[cleanup_index_generations()] Deleted old index generation: {}",
+                generation.index_name
+            );
+        }
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl EsQueryService for EsQueryServicePub {
+    #[doc = "정적색인용 새로운 인덱스를 생성해주는 함수 -> alias 이름 뒤에 생성시각을 붙여준다."]
+    /// # Arguments
+    /// * `index_schedule` - Index schedule information
+    ///
+    /// # Returns
+    /// * Result<String, anyhow::Error> - 생성한 인덱스 이름
+    async fn create_static_index(
+        &self,
+        index_schedule: &IndexSchedules,
+    ) -> Result<String, anyhow::Error> {
         /* === information of  index_schedule === */
         let index_alias_name: &String = index_schedule.index_name();
         let index_settings_path: &str = match index_schedule.setting_path() {
             Some(index_setting_path) => index_setting_path.as_str(),
             None => {
-                return Err(anyhow!(
-                    "[Error][store_static_index()] Please specify 'setting_path' for index"
+                return Err(IndexingError::Config(format!(
+                    "[create_static_index()] Please specify 'setting_path' for index '{}'",
+                    index_alias_name
                 ))
+                .into())
             }
         };
 
-        let es_batch_size: usize = *index_schedule.es_batch_size();
         /* ====================================== */
         let es_conn: ElasticConnGuard = get_elastic_guard_conn().await?;
 
@@ -85,78 +428,135 @@ impl EsQueryService for EsQueryServicePub {
         let json_body: Value = match read_json_from_file(index_settings_path) {
             Ok(json_body) => json_body,
             Err(e) => {
-                error!("[Error][post_indexing_data_by_bulk()] Failed to read 'index_settings' file.: {:?}", e);
-                return Err(anyhow!("[Error][post_indexing_data_by_bulk()] Failed to read 'index_settings' file.: {:?}", e));
+                error!(
+                    "[Error][create_static_index()] Failed to read 'index_settings' file.: {:?}",
+                    e
+                );
+                return Err(IndexingError::Config(format!(
+                    "[create_static_index()] Failed to read 'index_settings' file.: {:?}",
+                    e
+                ))
+                .into());
             }
         };
 
         es_conn.create_index(&new_index_name, &json_body).await?;
 
-        /* Bulk post the data to the index above at once. */
-        es_conn
-            .bulk_indexing_query(&new_index_name, data, es_batch_size)
-            .await?;
+        Ok(new_index_name)
+    }
 
-        /* 해당 인덱스가 있는지 없는지 확인해준다. */
+    #[doc = "문서들을 지정한 인덱스(alias)에 bulk 색인해주는 함수 -> 실패한 문서는 재시도 후 dead-letter 파일로 남긴다."]
+    /// # Arguments
+    /// * `index_schedule` - Index schedule information
+    /// * `index_name` - Name of the index (or alias) to be indexed
+    /// * `data` - Vector information to be indexed
+    ///
+    /// # Returns
+    /// * Result<usize, anyhow::Error> - 재시도 후에도 색인에 실패한 문서 수
+    async fn post_indexing_data_by_bulk<T: EsDocument + Debug>(
+        &self,
+        index_schedule: &IndexSchedules,
+        index_name: &str,
+        data: &[T],
+    ) -> Result<usize, anyhow::Error> {
+        if data.is_empty() {
+            return Ok(0);
+        }
+
+        self.bulk_indexing_with_retry(index_schedule, index_name, data)
+            .await
+    }
+
+    #[doc = "새로 색인한 인덱스를 검증한 뒤 alias 를 새로운 인덱스로 교체해주는 함수"]
+    /// # Arguments
+    /// * `index_schedule` - Index schedule information
+    /// * `new_index_name` - Name of the newly created index
+    /// * `source_cnt` - Number of source documents read from MySQL
+    ///
+    /// # Returns
+    /// * Result<(), anyhow::Error>
+    async fn swap_static_index_alias(
+        &self,
+        index_schedule: &IndexSchedules,
+        new_index_name: &str,
+        source_cnt: usize,
+    ) -> Result<(), anyhow::Error> {
+        let index_alias_name: &String = index_schedule.index_name();
+
+        let es_conn: ElasticConnGuard = get_elastic_guard_conn().await?;
+
+        /* 해당 인덱스가 있는지 없는지 확인해준다. -> 404 가 아닌 실패는 alias 를 잘못 교체하지 않도록 그대로 반환한다. */
         let index_exists_yn: bool = match es_conn.check_index_exist(index_alias_name).await {
             Ok(_index_exists_yn) => true,
-            Err(e) => {
-                error!("[Error][post_indexing_data_by_bulk()] An index starting with that name does not exist.: {}, {:?}", index_alias_name, e);
+            Err(e) if e.is_not_found() => {
+                info!("[swap_static_index_alias()] An index starting with that name does not exist.: {}, {:?}", index_alias_name, e);
                 false
             }
+            Err(e) => return Err(e.into()),
         };
 
-        if index_exists_yn {
-            /* 기존 인덱스가 존재하는 경우 */
+        /* 기존 인덱스가 존재하는 경우, alias 에 맵핑된 인덱스 이름을 가져와준다. */
+        let old_index_name: Option<String> = if index_exists_yn {
             let alias_resp: Value = es_conn
                 .get_indexes_mapping_by_alias(index_alias_name)
                 .await?;
 
-            let old_index_name: String;
-
-            if let Some(first_key) = alias_resp.as_object().and_then(|map| map.keys().next()) {
-                old_index_name = first_key.to_string();
-            } else {
-                return Err(anyhow!("[Error][post_indexing_data_by_bulk()] Failed to extract index name within 'index-alias'"));
+            match alias_resp.as_object().and_then(|map| map.keys().next()) {
+                Some(first_key) => Some(first_key.to_string()),
+                None => {
+                    return Err(anyhow!("[Error][swap_static_index_alias()] Failed to extract index name within 'index-alias'"));
+                }
             }
+        } else {
+            None
+        };
 
-            es_conn
-                .update_index_alias(index_alias_name, &new_index_name, &old_index_name)
-                .await?;
+        /* alias 를 교체하기 전에 새로운 인덱스가 정상적으로 색인되었는지 검증해준다. */
+        if let Err(e) = self
+            .validate_new_index(
+                &es_conn,
+                index_schedule,
+                new_index_name,
+                old_index_name.as_deref(),
+                source_cnt,
+            )
+            .await
+        {
+            error!(
+                "[Error][swap_static_index_alias()] The alias swap is blocked. '{}' is kept for inspection.: {:?}",
+                new_index_name, e
+            );
+            return Err(e);
+        }
 
-            es_conn.delete_query(&old_index_name).await?;
-        } else {
-            /* 기존 인덱스가 존재하지 않는 경우 -> 새로운 인덱스를 생성해준다. */
-            es_conn
-                .create_index_alias(index_alias_name, &new_index_name)
-                .await?;
+        match old_index_name {
+            Some(old_index_name) => {
+                /* 기존 인덱스가 존재하는 경우 */
+                es_conn
+                    .update_index_alias(index_alias_name, new_index_name, &old_index_name)
+                    .await?;
+            }
+            None => {
+                /* 기존 인덱스가 존재하지 않는 경우 -> 새로운 인덱스를 생성해준다. */
+                es_conn
+                    .create_index_alias(index_alias_name, new_index_name)
+                    .await?;
+            }
         }
 
         /* Functions to enable search immediately after index */
         es_conn.refresh_index(index_alias_name).await?;
 
-        Ok(())
-    }
-
-    #[doc = "Elasticsearch - dynamic index function"]
-    /// # Arguments
-    /// * `index_schedule` - Index schedule information
-    /// * `data` - Vector information to be indexed
-    ///
-    /// # Returns
-    /// * Result<(), anyhow::Error>
-    async fn post_indexing_data_by_bulk_dynamic<T: Serialize + Send + Sync + Debug>(
-        &self,
-        index_schedule: &IndexSchedules,
-        data: &[T],
-    ) -> Result<(), anyhow::Error> {
-        let index_alias_name: &String = index_schedule.index_name();
-        let es_batch_size: usize = *index_schedule.es_batch_size();
-
-        let es_conn: ElasticConnGuard = get_elastic_guard_conn().await?;
-        es_conn
-            .bulk_indexing_query(&index_alias_name, data, es_batch_size)
-            .await?;
+        /* 이전 세대의 인덱스는 롤백을 위해 retention_count 만큼 남겨두고 정리해준다. */
+        if let Err(e) = self
+            .cleanup_index_generations(&es_conn, index_schedule)
+            .await
+        {
+            error!(
+                "[Error][swap_static_index_alias()] Failed to clean up old index generations.: {:?}",
+                e
+            );
+        }
 
         Ok(())
     }
@@ -199,81 +599,155 @@ impl EsQueryService for EsQueryServicePub {
         Ok(timestamp)
     }
 
-    #[doc = "기존의 Elasticsearch 문서들을 update 해주는 함수"]
+    #[doc = "고유필드 값 목록에 해당하는 Elasticsearch 문서들을 삭제 해주는 함수"]
     /// # Arguments
     /// * `index_schedule` - Index schedule information
-    /// * `data` - Vector information to be indexed
+    /// * `field_values` - Unique field values to be deleted
     /// * `unique_field_name` - Name of a unique field
     ///
     /// # Returns
     /// * Result<(), anyhow::Error>
-    async fn update_index<T: Serialize + Send + Sync + Debug>(
+    async fn delete_index_by_field_values(
         &self,
         index_schedule: &IndexSchedules,
-        data: &[T],
+        field_values: &[i32],
         unique_field_name: &str,
     ) -> Result<(), anyhow::Error> {
         let index_name: &String = index_schedule.index_name();
+        let es_batch_size: usize = *index_schedule.es_batch_size();
 
         let es_conn: ElasticConnGuard = get_elastic_guard_conn().await?;
 
-        for item in data {
-            let json_value: Value = serde_json::to_value(&item)?;
-
-            let unique_value: i32 = json_value[&unique_field_name]
-                .as_i64()
-                .ok_or_else(|| anyhow!("[Error][update_index()] There was a problem converting data for 'unique_value'"))?
-                .try_into()?;
-
-            /* 기존 문서 삭제 */
+        for chunk in field_values.chunks(es_batch_size) {
             es_conn
-                .delete_query_where_field(index_name, unique_field_name, unique_value)
+                .delete_query_where_field_in(index_name, unique_field_name, chunk)
                 .await?;
-
-            /* 새로운 문서 색인 */
-            es_conn.post_query_struct::<T>(item, index_name).await?;
         }
 
         Ok(())
     }
 
-    #[doc = "기존의 Elasticsearch 문서들을 삭제 해주는 함수"]
+    #[doc = "alias 에 해당하는 정적색인 인덱스 세대 목록을 가져와주는 함수"]
     /// # Arguments
-    /// * `index_schedule` - Index schedule information
-    /// * `data` - Vector information to be indexed
-    /// * `unique_field_name` - Name of a unique field
+    /// * `index_alias_name` - index alias name
+    ///
+    /// # Returns
+    /// * Result<Vec<IndexGeneration>, anyhow::Error>
+    async fn get_index_generations(
+        &self,
+        index_alias_name: &str,
+    ) -> Result<Vec<IndexGeneration>, anyhow::Error> {
+        let es_conn: ElasticConnGuard = get_elastic_guard_conn().await?;
+
+        self.fetch_index_generations(&es_conn, index_alias_name)
+            .await
+    }
+
+    #[doc = "alias 를 이전 세대의 인덱스로 원자적으로 되돌려주는 함수"]
+    /// # Arguments
+    /// * `index_alias_name` - index alias name
+    /// * `target_index_name` - Index name to be mapped to the alias again
     ///
     /// # Returns
     /// * Result<(), anyhow::Error>
-    async fn delete_index<T: Serialize + Send + Sync + Debug>(
+    async fn rollback_index_alias(
         &self,
-        index_schedule: &IndexSchedules,
-        data: &[T],
-        unique_field_name: &str,
+        index_alias_name: &str,
+        target_index_name: &str,
     ) -> Result<(), anyhow::Error> {
-        let index_name: &String = index_schedule.index_name();
-
         let es_conn: ElasticConnGuard = get_elastic_guard_conn().await?;
 
-        for document in data {
-            let json_value: Value = serde_json::to_value(&document)?;
+        let generations: Vec<IndexGeneration> = self
+            .fetch_index_generations(&es_conn, index_alias_name)
+            .await?;
+
+        if !generations
+            .iter()
+            .any(|generation| generation.index_name == target_index_name)
+        {
+            return Err(anyhow!(
+                "[Error][rollback_index_alias()] '{}' is not a generation of '{}'",
+                target_index_name,
+                index_alias_name
+            ));
+        }
+
+        match generations
+            .iter()
+            .find(|generation| generation.is_alias_target)
+        {
+            Some(current) => {
+                es_conn
+                    .update_index_alias(index_alias_name, target_index_name, &current.index_name)
+                    .await?;
+            }
+            None => {
+                es_conn
+                    .create_index_alias(index_alias_name, target_index_name)
+                    .await?;
+            }
+        }
+
+        es_conn.refresh_index(index_alias_name).await?;
 
-            let unique_value: i32 = json_value[&unique_field_name]
-                .as_i64()
-                .ok_or_else(|| anyhow!("[Error][delete_index()] There was a problem converting data for 'unique_value'"))?
-                .try_into()?;
+        Ok(())
+    }
 
-            println!("unique_value: {}", unique_value);
+    #[doc = "alias 에 맵핑되지 않은 세대의 인덱스를 삭제해주는 함수 -> 취소된 정적색인이 만든 인덱스를 정리할 때 사용한다."]
+    /// # Arguments
+    /// * `index_alias_name` - index alias name
+    /// * `index_name` - Index name to delete
+    ///
+    /// # Returns
+    /// * Result<(), anyhow::Error>
+    async fn delete_index_generation(
+        &self,
+        index_alias_name: &str,
+        index_name: &str,
+    ) -> Result<(), anyhow::Error> {
+        let es_conn: ElasticConnGuard = get_elastic_guard_conn().await?;
 
-            /* 기존 문서 삭제 */
-            es_conn
-                .delete_query_where_field(index_name, unique_field_name, unique_value)
-                .await?;
+        let generations: Vec<IndexGeneration> = self
+            .fetch_index_generations(&es_conn, index_alias_name)
+            .await?;
+
+        let generation: &IndexGeneration = generations
+            .iter()
+            .find(|generation| generation.index_name == index_name)
+            .ok_or_else(|| {
+                anyhow!(
+                    "[Error][delete_index_generation()] '{}' is not a generation of '{}'",
+                    index_name,
+                    index_alias_name
+                )
+            })?;
+
+        /* alias 교체까지 끝난 인덱스는 서비스중이므로 삭제하지 않는다. */
+        if generation.is_alias_target {
+            return Err(anyhow!(
+                "[Error][delete_index_generation()] '{}' is mapped to '{}'",
+                index_name,
+                index_alias_name
+            ));
         }
 
+        es_conn.delete_query(index_name).await?;
+
         Ok(())
     }
 
+    #[doc = "인덱스(alias)의 문서 수를 가져와주는 함수"]
+    /// # Arguments
+    /// * `index_name` - Index or alias name
+    ///
+    /// # Returns
+    /// * Result<u64, anyhow::Error>
+    async fn get_index_doc_count(&self, index_name: &str) -> Result<u64, anyhow::Error> {
+        let es_conn: ElasticConnGuard = get_elastic_guard_conn().await?;
+
+        Ok(es_conn.get_index_count(index_name).await?)
+    }
+
     async fn get_test(&self) -> Result<(), anyhow::Error> {
         let es_conn: ElasticConnGuard = get_elastic_guard_conn().await?;
 
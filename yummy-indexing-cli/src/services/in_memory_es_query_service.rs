@@ -0,0 +1,346 @@
+use crate::common::*;
+
+use std::collections::BTreeMap;
+
+use crate::configuration::index_schedules_config::*;
+
+use crate::models::es_document::*;
+use crate::models::index_generation::*;
+use crate::models::indexing_error::*;
+
+use crate::services::call_log::*;
+use crate::services::es_query_service::*;
+
+use crate::utils_module::time_utils::*;
+
+#[doc = "InMemoryEsQueryService 가 만들어주는 첫번째 인덱스 세대의 생성시각 (YYYYmmddHHMMSS)"]
+const FIRST_GENERATION: u64 = 20250101000000;
+
+#[doc = "InMemoryEsQueryService 가 들고있는 Elasticsearch 대신의 데이터"]
+#[derive(Debug, Default)]
+struct InMemoryEsState {
+    /* 인덱스 이름 -> (_id -> 문서) */
+    indices: HashMap<String, BTreeMap<String, Value>>,
+    /* alias -> 맵핑된 인덱스 이름 */
+    aliases: HashMap<String, String>,
+    /* 지금까지 만든 정적색인 인덱스 세대 수 */
+    generation_cnt: u64,
+    /* true 이면 alias 교체가 검증 실패로 막힌다. */
+    fail_alias_swap: bool,
+    /* true 이면 alias 교체가 끝나지 않는다. (취소 테스트용) */
+    stall_alias_swap: bool,
+}
+
+impl InMemoryEsState {
+    #[doc = "alias 인 경우 맵핑된 인덱스 이름으로 바꿔주는 함수"]
+    fn resolve(&self, index_name: &str) -> String {
+        self.aliases
+            .get(index_name)
+            .cloned()
+            .unwrap_or_else(|| index_name.to_string())
+    }
+
+    #[doc = "고유필드 값이 일치하는 문서들을 지워주는 함수"]
+    fn remove_where_field(&mut self, index_name: &str, unique_field_name: &str, values: &[i32]) {
+        let index_name: String = self.resolve(index_name);
+
+        if let Some(documents) = self.indices.get_mut(&index_name) {
+            documents.retain(|_, document| {
+                !document[unique_field_name]
+                    .as_i64()
+                    .is_some_and(|value| values.iter().any(|target| *target as i64 == value))
+            });
+        }
+    }
+}
+
+#[doc = "테스트용 EsQueryService -> Elasticsearch 대신 메모리에 문서를 들고 있고, 호출된 함수를 CallLog 에 남긴다."]
+#[derive(Debug, Default)]
+pub struct InMemoryEsQueryService {
+    call_log: CallLog,
+    state: std::sync::Mutex<InMemoryEsState>,
+}
+
+impl InMemoryEsQueryService {
+    #[doc = "다른 서비스들과 호출 기록을 공유하는 InMemoryEsQueryService 를 만들어주는 함수"]
+    /// # Arguments
+    /// * `call_log` - 공유할 호출 기록
+    pub fn new(call_log: CallLog) -> Self {
+        InMemoryEsQueryService {
+            call_log,
+            state: std::sync::Mutex::new(InMemoryEsState::default()),
+        }
+    }
+
+    fn state(&self) -> std::sync::MutexGuard<'_, InMemoryEsState> {
+        self.state
+            .lock()
+            .expect("[Error][InMemoryEsQueryService::state()] state is poisoned")
+    }
+
+    #[doc = "인덱스를 만들고 alias 를 맵핑해주는 함수"]
+    pub fn with_alias(self, index_alias_name: &str, index_name: &str) -> Self {
+        {
+            let mut state = self.state();
+            state.indices.entry(index_name.to_string()).or_default();
+            state
+                .aliases
+                .insert(index_alias_name.to_string(), index_name.to_string());
+        }
+        self
+    }
+
+    #[doc = "인덱스(alias)에 문서를 넣어주는 함수"]
+    pub fn with_document(self, index_name: &str, document_id: &str, document: Value) -> Self {
+        {
+            let mut state = self.state();
+            let index_name: String = state.resolve(index_name);
+            state
+                .indices
+                .entry(index_name)
+                .or_default()
+                .insert(document_id.to_string(), document);
+        }
+        self
+    }
+
+    #[doc = "alias 교체가 검증 실패로 막히도록 설정해주는 함수"]
+    pub fn with_failing_alias_swap(self) -> Self {
+        self.state().fail_alias_swap = true;
+        self
+    }
+
+    #[doc = "alias 교체가 끝나지 않고 멈춰있도록 설정해주는 함수"]
+    pub fn with_stalled_alias_swap(self) -> Self {
+        self.state().stall_alias_swap = true;
+        self
+    }
+
+    #[doc = "alias 에 맵핑된 인덱스 이름을 가져와주는 함수"]
+    pub fn alias_target(&self, index_alias_name: &str) -> Option<String> {
+        self.state().aliases.get(index_alias_name).cloned()
+    }
+
+    #[doc = "인덱스(alias)의 문서들을 _id 순서로 가져와주는 함수"]
+    pub fn documents(&self, index_name: &str) -> Vec<Value> {
+        let state = self.state();
+
+        state
+            .indices
+            .get(&state.resolve(index_name))
+            .map(|documents| documents.values().cloned().collect())
+            .unwrap_or_default()
+    }
+}
+
+#[async_trait]
+impl EsQueryService for InMemoryEsQueryService {
+    async fn create_static_index(
+        &self,
+        index_schedule: &IndexSchedules,
+    ) -> Result<String, anyhow::Error> {
+        self.call_log.record("create_static_index");
+
+        let mut state = self.state();
+        let new_index_name: String = format!(
+            "{}-{}",
+            index_schedule.index_name(),
+            FIRST_GENERATION + state.generation_cnt
+        );
+
+        state.generation_cnt += 1;
+        state
+            .indices
+            .insert(new_index_name.clone(), BTreeMap::new());
+
+        Ok(new_index_name)
+    }
+
+    async fn post_indexing_data_by_bulk<T: EsDocument + Debug>(
+        &self,
+        _index_schedule: &IndexSchedules,
+        index_name: &str,
+        data: &[T],
+    ) -> Result<usize, anyhow::Error> {
+        self.call_log.record("post_indexing_data_by_bulk");
+
+        let mut state = self.state();
+        let index_name: String = state.resolve(index_name);
+        let documents: &mut BTreeMap<String, Value> = state.indices.entry(index_name).or_default();
+
+        for document in data {
+            documents.insert(document.document_id(), serde_json::to_value(document)?);
+        }
+
+        Ok(0)
+    }
+
+    async fn swap_static_index_alias(
+        &self,
+        index_schedule: &IndexSchedules,
+        new_index_name: &str,
+        _source_cnt: usize,
+    ) -> Result<(), anyhow::Error> {
+        self.call_log.record("swap_static_index_alias");
+
+        let stall_alias_swap: bool = self.state().stall_alias_swap;
+
+        if stall_alias_swap {
+            std::future::pending::<()>().await;
+        }
+
+        let mut state = self.state();
+
+        if state.fail_alias_swap {
+            return Err(IndexingError::Validation {
+                index_name: new_index_name.to_string(),
+                reason: "was rejected by the in-memory alias swap.".to_string(),
+            }
+            .into());
+        }
+
+        if !state.indices.contains_key(new_index_name) {
+            return Err(anyhow!(
+                "[Error][swap_static_index_alias()] '{}' does not exist",
+                new_index_name
+            ));
+        }
+
+        state.aliases.insert(
+            index_schedule.index_name().to_string(),
+            new_index_name.to_string(),
+        );
+
+        Ok(())
+    }
+
+    async fn get_recent_index_datetime(
+        &self,
+        index_schedule: &IndexSchedules,
+        timestamp_field: &str,
+    ) -> Result<NaiveDateTime, anyhow::Error> {
+        self.call_log.record("get_recent_index_datetime");
+
+        let timestamp_str: String = self
+            .documents(index_schedule.index_name())
+            .iter()
+            .filter_map(|document| document[timestamp_field].as_str().map(str::to_string))
+            .max()
+            .ok_or_else(|| {
+                anyhow!("[Error][get_recent_index_datetime()] Failed to parse 'timestamp'")
+            })?;
+
+        get_naive_datetime_from_str(&timestamp_str, "%Y-%m-%dT%H:%M:%SZ")
+    }
+
+    async fn delete_index_by_field_values(
+        &self,
+        index_schedule: &IndexSchedules,
+        field_values: &[i32],
+        unique_field_name: &str,
+    ) -> Result<(), anyhow::Error> {
+        self.call_log.record("delete_index_by_field_values");
+
+        self.state().remove_where_field(
+            index_schedule.index_name(),
+            unique_field_name,
+            field_values,
+        );
+
+        Ok(())
+    }
+
+    async fn get_index_generations(
+        &self,
+        index_alias_name: &str,
+    ) -> Result<Vec<IndexGeneration>, anyhow::Error> {
+        self.call_log.record("get_index_generations");
+
+        let state = self.state();
+        let prefix: String = format!("{}-", index_alias_name);
+        let alias_target: Option<&String> = state.aliases.get(index_alias_name);
+
+        let mut generations: Vec<IndexGeneration> = state
+            .indices
+            .iter()
+            .filter_map(|(index_name, documents)| {
+                let creation_date: &str = index_name.strip_prefix(&prefix)?;
+
+                Some(IndexGeneration::new(
+                    index_name.clone(),
+                    documents.len() as u64,
+                    creation_date.to_string(),
+                    alias_target == Some(index_name),
+                ))
+            })
+            .collect();
+
+        /* 최신 세대가 앞에 오도록 정렬 */
+        generations.sort_by(|a, b| b.index_name.cmp(&a.index_name));
+
+        Ok(generations)
+    }
+
+    async fn rollback_index_alias(
+        &self,
+        index_alias_name: &str,
+        target_index_name: &str,
+    ) -> Result<(), anyhow::Error> {
+        self.call_log.record("rollback_index_alias");
+
+        let mut state = self.state();
+
+        if !target_index_name.starts_with(&format!("{}-", index_alias_name))
+            || !state.indices.contains_key(target_index_name)
+        {
+            return Err(anyhow!(
+                "[Error][rollback_index_alias()] '{}' is not a generation of '{}'",
+                target_index_name,
+                index_alias_name
+            ));
+        }
+
+        state
+            .aliases
+            .insert(index_alias_name.to_string(), target_index_name.to_string());
+
+        Ok(())
+    }
+
+    async fn delete_index_generation(
+        &self,
+        index_alias_name: &str,
+        index_name: &str,
+    ) -> Result<(), anyhow::Error> {
+        self.call_log.record("delete_index_generation");
+
+        let mut state = self.state();
+
+        if !index_name.starts_with(&format!("{}-", index_alias_name))
+            || !state.indices.contains_key(index_name)
+            || state.aliases.get(index_alias_name).map(String::as_str) == Some(index_name)
+        {
+            return Err(anyhow!(
+                "[Error][delete_index_generation()] '{}' is not an unmapped generation of '{}'",
+                index_name,
+                index_alias_name
+            ));
+        }
+
+        state.indices.remove(index_name);
+
+        Ok(())
+    }
+
+    async fn get_index_doc_count(&self, index_name: &str) -> Result<u64, anyhow::Error> {
+        self.call_log.record("get_index_doc_count");
+
+        Ok(self.documents(index_name).len() as u64)
+    }
+
+    async fn get_test(&self) -> Result<(), anyhow::Error> {
+        self.call_log.record("get_test");
+
+        Ok(())
+    }
+}
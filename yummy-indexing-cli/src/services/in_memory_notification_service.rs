@@ -0,0 +1,52 @@
+use crate::common::*;
+
+use crate::configuration::index_schedules_config::*;
+
+use crate::models::run_error::*;
+
+use crate::services::call_log::*;
+use crate::services::notification_service::*;
+
+#[doc = "테스트용 NotificationService -> 알림을 보내지 않고 호출된 함수만 CallLog 에 남긴다."]
+#[derive(Debug, Default, new)]
+pub struct InMemoryNotificationService {
+    call_log: CallLog,
+}
+
+#[async_trait]
+impl NotificationService for InMemoryNotificationService {
+    async fn notify_run_failure(
+        &self,
+        _index_schedule: &IndexSchedules,
+        _attempt: u32,
+        _error_class: RunErrorClass,
+        _error: &anyhow::Error,
+    ) {
+        self.call_log.record("notify_run_failure");
+    }
+
+    async fn notify_validation_blocked(
+        &self,
+        _index_schedule: &IndexSchedules,
+        _error: &anyhow::Error,
+    ) {
+        self.call_log.record("notify_validation_blocked");
+    }
+
+    async fn notify_watermark_lag(
+        &self,
+        _index_schedule: &IndexSchedules,
+        _watermark: NaiveDateTime,
+        _lag_secs: i64,
+    ) {
+        self.call_log.record("notify_watermark_lag");
+    }
+
+    async fn notify_daily_summary(
+        &self,
+        _summary_time: &str,
+        _doc_counts: &[(String, Result<u64, anyhow::Error>)],
+    ) {
+        self.call_log.record("notify_daily_summary");
+    }
+}
@@ -0,0 +1,478 @@
+use crate::common::*;
+
+use std::collections::BTreeMap;
+
+use crate::configuration::index_schedules_config::*;
+
+use crate::models::indexing_error::*;
+use crate::models::run_error::*;
+use crate::models::run_report::*;
+use crate::models::store_to_elastic::*;
+use crate::models::store_types::*;
+
+use crate::services::call_log::*;
+use crate::services::query_service::*;
+
+use crate::utils_module::time_utils::*;
+
+use crate::entity::{elastic_index_lock_tbl, elastic_index_run_history_tbl};
+
+#[doc = "InMemoryQueryService 가 들고있는 MySQL 대신의 데이터"]
+#[derive(Debug, Default)]
+struct InMemoryQueryState {
+    /* 색인 대상(use_yn = 'Y') 상점: seq -> 상점 이름 */
+    stores: BTreeMap<i32, String>,
+    /* 마지막 색인 이후 변경된 상점 seq */
+    changed_seqs: BTreeSet<i32>,
+    /* 마지막 색인 이후 비활성화(use_yn = 'N') 된 상점 seq */
+    removed_seqs: BTreeSet<i32>,
+    /* seq -> (대분류, 소분류) 목록 */
+    store_types: HashMap<i32, Vec<(i32, i32)>>,
+    /* 인덱스 이름 -> 마지막 색인 시각 */
+    recent_dates: HashMap<String, NaiveDateTime>,
+    locks: HashMap<String, elastic_index_lock_tbl::Model>,
+    run_histories: Vec<elastic_index_run_history_tbl::Model>,
+}
+
+#[doc = "테스트용 QueryService -> MySQL 대신 메모리에 데이터를 들고 있고, 호출된 함수를 CallLog 에 남긴다."]
+#[derive(Debug, Default)]
+pub struct InMemoryQueryService {
+    call_log: CallLog,
+    state: std::sync::Mutex<InMemoryQueryState>,
+}
+
+impl InMemoryQueryService {
+    #[doc = "다른 서비스들과 호출 기록을 공유하는 InMemoryQueryService 를 만들어주는 함수"]
+    /// # Arguments
+    /// * `call_log` - 공유할 호출 기록
+    pub fn new(call_log: CallLog) -> Self {
+        InMemoryQueryService {
+            call_log,
+            state: std::sync::Mutex::new(InMemoryQueryState::default()),
+        }
+    }
+
+    fn state(&self) -> std::sync::MutexGuard<'_, InMemoryQueryState> {
+        self.state
+            .lock()
+            .expect("[Error][InMemoryQueryService::state()] state is poisoned")
+    }
+
+    #[doc = "색인 대상 상점을 추가해주는 함수"]
+    pub fn with_store(self, seq: i32, name: &str) -> Self {
+        self.state().stores.insert(seq, name.to_string());
+        self
+    }
+
+    #[doc = "상점에 대분류/소분류를 추가해주는 함수"]
+    pub fn with_store_type(self, seq: i32, major_type: i32, sub_type: i32) -> Self {
+        self.state()
+            .store_types
+            .entry(seq)
+            .or_default()
+            .push((major_type, sub_type));
+        self
+    }
+
+    #[doc = "마지막 색인 이후 변경된 상점으로 표시해주는 함수"]
+    pub fn mark_changed(self, seq: i32) -> Self {
+        self.state().changed_seqs.insert(seq);
+        self
+    }
+
+    #[doc = "마지막 색인 이후 비활성화된 상점으로 표시해주는 함수 -> 더이상 색인 대상이 아니다."]
+    pub fn mark_removed(self, seq: i32) -> Self {
+        {
+            let mut state = self.state();
+            state.stores.remove(&seq);
+            state.changed_seqs.remove(&seq);
+            state.removed_seqs.insert(seq);
+        }
+        self
+    }
+
+    #[doc = "인덱스의 마지막 색인 시각을 지정해주는 함수"]
+    pub fn with_recent_date(self, index_name: &str, recent_date: NaiveDateTime) -> Self {
+        self.state()
+            .recent_dates
+            .insert(index_name.to_string(), recent_date);
+        self
+    }
+
+    #[doc = "인덱스의 마지막 색인 시각을 가져와주는 함수"]
+    pub fn recent_date(&self, index_name: &str) -> Option<NaiveDateTime> {
+        self.state().recent_dates.get(index_name).copied()
+    }
+
+    #[doc = "seq 목록을 last_seq 이후부터 sql_batch_size 만큼 잘라서 상점 행으로 만들어주는 함수"]
+    /// # Arguments
+    /// * `seqs` - 대상 상점 seq (오름차순)
+    /// * `batch_size` - 페이지 크기
+    /// * `last_seq` - 이전 페이지의 마지막 seq
+    ///
+    /// # Returns
+    /// * Vec<StoreResult>
+    fn get_store_page(
+        &self,
+        seqs: Vec<i32>,
+        batch_size: usize,
+        last_seq: Option<i32>,
+    ) -> Vec<StoreResult> {
+        let state = self.state();
+
+        seqs.into_iter()
+            .filter(|seq| last_seq.is_none_or(|last_seq| *seq > last_seq))
+            .filter_map(|seq| state.stores.get(&seq).map(|name| (seq, name.clone())))
+            .take(batch_size)
+            .map(|(seq, name)| StoreResult {
+                seq,
+                name,
+                r#type: None,
+                address: None,
+                lat: Decimal::ZERO,
+                lng: Decimal::ZERO,
+                zero_possible: false,
+                recommend_name: None,
+                location_city: None,
+                location_county: None,
+                location_district: None,
+            })
+            .collect()
+    }
+}
+
+#[async_trait]
+impl QueryService for InMemoryQueryService {
+    async fn get_store_by_batch(
+        &self,
+        batch_size: usize,
+        _query_filter: Condition,
+        _cur_utc_date: NaiveDateTime,
+        last_seq: Option<i32>,
+    ) -> Result<Vec<StoreResult>, IndexingError> {
+        self.call_log.record("get_store_by_batch");
+
+        let seqs: Vec<i32> = self.state().stores.keys().copied().collect();
+
+        Ok(self.get_store_page(seqs, batch_size, last_seq))
+    }
+
+    async fn get_all_store_table(
+        &self,
+        index_schedule: &IndexSchedules,
+        cur_utc_date: NaiveDateTime,
+        last_seq: Option<i32>,
+    ) -> Result<Vec<DistinctStoreResult>, IndexingError> {
+        self.call_log.record("get_all_store_table");
+
+        let seqs: Vec<i32> = self.state().stores.keys().copied().collect();
+        let stores: Vec<StoreResult> =
+            self.get_store_page(seqs, *index_schedule.sql_batch_size(), last_seq);
+
+        self.get_distinct_store_table(&stores, cur_utc_date)
+    }
+
+    async fn get_specific_store_table(
+        &self,
+        index_schedule: &IndexSchedules,
+        cur_utc_date: NaiveDateTime,
+        _recent_datetime: NaiveDateTime,
+        last_seq: Option<i32>,
+    ) -> Result<Vec<DistinctStoreResult>, IndexingError> {
+        self.call_log.record("get_specific_store_table");
+
+        let seqs: Vec<i32> = self.state().changed_seqs.iter().copied().collect();
+        let stores: Vec<StoreResult> =
+            self.get_store_page(seqs, *index_schedule.sql_batch_size(), last_seq);
+
+        self.get_distinct_store_table(&stores, cur_utc_date)
+    }
+
+    fn get_distinct_store_table(
+        &self,
+        stores: &[StoreResult],
+        cur_utc_date: NaiveDateTime,
+    ) -> Result<Vec<DistinctStoreResult>, IndexingError> {
+        QueryServicePub::new().get_distinct_store_table(stores, cur_utc_date)
+    }
+
+    async fn get_recent_date_from_elastic_index_info(
+        &self,
+        index_schedule: &IndexSchedules,
+    ) -> Result<NaiveDateTime, IndexingError> {
+        self.call_log
+            .record("get_recent_date_from_elastic_index_info");
+
+        self.recent_date(index_schedule.index_name())
+            .ok_or_else(|| {
+                IndexingError::Config(format!(
+                "[get_recent_date_from_elastic_index_info()] No 'elastic_index_info' row for '{}'",
+                index_schedule.index_name()
+            ))
+            })
+    }
+
+    async fn update_recent_date_to_elastic_index_info(
+        &self,
+        index_schedule: &IndexSchedules,
+        new_datetime: NaiveDateTime,
+    ) -> Result<(), IndexingError> {
+        self.call_log
+            .record("update_recent_date_to_elastic_index_info");
+
+        self.state()
+            .recent_dates
+            .insert(index_schedule.index_name().to_string(), new_datetime);
+
+        Ok(())
+    }
+
+    async fn get_store_types(
+        &self,
+        store_seqs: Option<Vec<i32>>,
+    ) -> Result<StoreTypesMap, IndexingError> {
+        self.call_log.record("get_store_types");
+
+        let state = self.state();
+
+        let seqs: Vec<i32> = match store_seqs {
+            Some(store_seqs) => store_seqs,
+            None => state.store_types.keys().copied().collect(),
+        };
+
+        let mut store_types_map: StoreTypesMap = StoreTypesMap::new(
+            HashMap::new(),
+            HashMap::new(),
+            HashMap::new(),
+            HashMap::new(),
+        );
+
+        for seq in seqs {
+            let Some(store_types) = state.store_types.get(&seq) else {
+                continue;
+            };
+
+            for (major_type, sub_type) in store_types {
+                let major_types: &mut Vec<i32> =
+                    store_types_map.store_type_major_map.entry(seq).or_default();
+
+                if !major_types.contains(major_type) {
+                    major_types.push(*major_type);
+                    store_types_map
+                        .store_type_major_name_map
+                        .entry(seq)
+                        .or_default()
+                        .push(format!("major-{}", major_type));
+                }
+
+                store_types_map
+                    .store_type_sub_map
+                    .entry(seq)
+                    .or_default()
+                    .push(*sub_type);
+                store_types_map
+                    .store_type_sub_name_map
+                    .entry(seq)
+                    .or_default()
+                    .push(format!("sub-{}", sub_type));
+            }
+        }
+
+        Ok(store_types_map)
+    }
+
+    async fn get_removed_store_seqs(
+        &self,
+        _recent_datetime: NaiveDateTime,
+    ) -> Result<Vec<i32>, IndexingError> {
+        self.call_log.record("get_removed_store_seqs");
+
+        Ok(self.state().removed_seqs.iter().copied().collect())
+    }
+
+    async fn try_acquire_index_lock(
+        &self,
+        index_name: &str,
+        lock_owner: &str,
+        lease_secs: u64,
+    ) -> Result<bool, IndexingError> {
+        self.call_log.record("try_acquire_index_lock");
+
+        let now: NaiveDateTime = get_current_utc_naive_datetime();
+        let mut state = self.state();
+
+        if let Some(lock) = state.locks.get(index_name) {
+            if lock.lock_owner != lock_owner && lock.lock_expire_dt > now {
+                return Ok(false);
+            }
+        }
+
+        state.locks.insert(
+            index_name.to_string(),
+            elastic_index_lock_tbl::Model {
+                index_name: index_name.to_string(),
+                lock_owner: lock_owner.to_string(),
+                lock_expire_dt: now + chrono::Duration::seconds(lease_secs as i64),
+                reg_dt: now,
+                chg_dt: now,
+            },
+        );
+
+        Ok(true)
+    }
+
+    async fn renew_index_lock(
+        &self,
+        index_name: &str,
+        lock_owner: &str,
+        lease_secs: u64,
+    ) -> Result<bool, IndexingError> {
+        self.call_log.record("renew_index_lock");
+
+        let now: NaiveDateTime = get_current_utc_naive_datetime();
+
+        match self.state().locks.get_mut(index_name) {
+            Some(lock) if lock.lock_owner == lock_owner => {
+                lock.lock_expire_dt = now + chrono::Duration::seconds(lease_secs as i64);
+                lock.chg_dt = now;
+                Ok(true)
+            }
+            _ => Ok(false),
+        }
+    }
+
+    async fn release_index_lock(
+        &self,
+        index_name: &str,
+        lock_owner: &str,
+    ) -> Result<(), IndexingError> {
+        self.call_log.record("release_index_lock");
+
+        let mut state = self.state();
+
+        if state
+            .locks
+            .get(index_name)
+            .is_some_and(|lock| lock.lock_owner == lock_owner)
+        {
+            state.locks.remove(index_name);
+        }
+
+        Ok(())
+    }
+
+    async fn get_index_lock(
+        &self,
+        index_name: &str,
+    ) -> Result<Option<elastic_index_lock_tbl::Model>, IndexingError> {
+        self.call_log.record("get_index_lock");
+
+        Ok(self.state().locks.get(index_name).cloned())
+    }
+
+    async fn insert_run_history(
+        &self,
+        index_schedule: &IndexSchedules,
+        run_trigger: RunTrigger,
+        attempt: u32,
+        start_dt: NaiveDateTime,
+    ) -> Result<i64, IndexingError> {
+        self.call_log.record("insert_run_history");
+
+        let mut state = self.state();
+        let run_id: i64 = state.run_histories.len() as i64 + 1;
+
+        state
+            .run_histories
+            .push(elastic_index_run_history_tbl::Model {
+                run_id,
+                index_name: index_schedule.index_name().to_string(),
+                function_name: index_schedule.function_name().to_string(),
+                indexing_type: index_schedule.indexing_type().to_string(),
+                run_trigger: run_trigger.as_str().to_string(),
+                run_status: RUN_STATUS_RUNNING.to_string(),
+                attempt: attempt as i32,
+                start_dt,
+                end_dt: None,
+                rows_read: 0,
+                documents_written: 0,
+                documents_deleted: 0,
+                documents_failed: 0,
+                generated_index_name: None,
+                error_class: None,
+                error_message: None,
+            });
+
+        Ok(run_id)
+    }
+
+    async fn finish_run_history(
+        &self,
+        run_id: i64,
+        run_report: &RunReport,
+        run_status: &str,
+        end_dt: NaiveDateTime,
+        error_class: Option<RunErrorClass>,
+        error_message: Option<String>,
+    ) -> Result<(), IndexingError> {
+        self.call_log.record("finish_run_history");
+
+        if let Some(run_history) = self
+            .state()
+            .run_histories
+            .iter_mut()
+            .find(|run_history| run_history.run_id == run_id)
+        {
+            run_history.run_status = run_status.to_string();
+            run_history.end_dt = Some(end_dt);
+            run_history.rows_read = run_report.rows_read as i64;
+            run_history.documents_written = run_report.documents_written as i64;
+            run_history.documents_deleted = run_report.documents_deleted as i64;
+            run_history.documents_failed = run_report.documents_failed as i64;
+            run_history.generated_index_name = run_report.generated_index_name.clone();
+            run_history.error_class =
+                error_class.map(|error_class| error_class.as_str().to_string());
+            run_history.error_message = error_message;
+        }
+
+        Ok(())
+    }
+
+    async fn get_run_histories(
+        &self,
+        index_name: &str,
+        limit: u64,
+    ) -> Result<Vec<elastic_index_run_history_tbl::Model>, IndexingError> {
+        self.call_log.record("get_run_histories");
+
+        Ok(self
+            .state()
+            .run_histories
+            .iter()
+            .rev()
+            .filter(|run_history| run_history.index_name == index_name)
+            .take(limit as usize)
+            .cloned()
+            .collect())
+    }
+
+    async fn get_last_successful_run(
+        &self,
+        index_name: &str,
+        function_name: &str,
+    ) -> Result<Option<elastic_index_run_history_tbl::Model>, IndexingError> {
+        self.call_log.record("get_last_successful_run");
+
+        Ok(self
+            .state()
+            .run_histories
+            .iter()
+            .rev()
+            .find(|run_history| {
+                run_history.index_name == index_name
+                    && run_history.function_name == function_name
+                    && run_history.run_status == RUN_STATUS_SUCCESS
+            })
+            .cloned())
+    }
+}
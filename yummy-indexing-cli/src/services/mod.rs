@@ -1,2 +1,12 @@
 pub mod es_query_service;
+pub mod notification_service;
 pub mod query_service;
+
+#[cfg(test)]
+pub mod call_log;
+#[cfg(test)]
+pub mod in_memory_es_query_service;
+#[cfg(test)]
+pub mod in_memory_notification_service;
+#[cfg(test)]
+pub mod in_memory_query_service;
@@ -0,0 +1,277 @@
+use crate::common::*;
+
+use crate::configuration::index_schedules_config::*;
+
+use crate::models::indexing_error::*;
+use crate::models::run_error::*;
+
+use crate::repository::telegram_repository::*;
+
+use crate::utils_module::time_utils::*;
+
+#[doc = "같은 알림을 다시 보내기까지의 최소 간격(초) 기본값"]
+const DEFAULT_ALERT_INTERVAL_SECS: u64 = 1800;
+
+#[doc = "Telegram 메시지 하나에 담을 에러 내용의 최대 길이"]
+const MAX_ERROR_MESSAGE_LEN: usize = 1000;
+
+#[async_trait]
+pub trait NotificationService: Send + Sync {
+    async fn notify_run_failure(
+        &self,
+        index_schedule: &IndexSchedules,
+        attempt: u32,
+        error_class: RunErrorClass,
+        error: &anyhow::Error,
+    );
+    async fn notify_validation_blocked(
+        &self,
+        index_schedule: &IndexSchedules,
+        error: &anyhow::Error,
+    );
+    async fn notify_watermark_lag(
+        &self,
+        index_schedule: &IndexSchedules,
+        watermark: NaiveDateTime,
+        lag_secs: i64,
+    );
+    async fn notify_daily_summary(
+        &self,
+        summary_time: &str,
+        doc_counts: &[(String, Result<u64, anyhow::Error>)],
+    );
+}
+
+#[doc = "알림 종류별 최근 전송 상태 -> 반복되는 알림을 묶어서 보내기 위해 사용한다."]
+#[derive(Debug)]
+struct AlertState {
+    last_sent: Instant,
+    suppressed_cnt: usize,
+}
+
+#[derive(Debug, new)]
+pub struct NotificationServicePub {
+    #[new(value = "get_alert_interval()")]
+    alert_interval: Duration,
+    #[new(default)]
+    alert_states: Mutex<HashMap<String, AlertState>>,
+}
+
+impl NotificationServicePub {
+    #[doc = "같은 알림이 최근에 전송되었는지 확인해주는 함수 -> 전송해야 하면 그동안 생략된 알림 수를 돌려준다."]
+    /// # Arguments
+    /// * `alert_key` - 알림 종류를 구분하는 키
+    ///
+    /// # Returns
+    /// * Option<usize> - None 이면 이번 알림은 생략한다.
+    async fn check_alert_interval(&self, alert_key: &str) -> Option<usize> {
+        let mut alert_states: MutexGuard<'_, HashMap<String, AlertState>> =
+            self.alert_states.lock().await;
+
+        match alert_states.get_mut(alert_key) {
+            Some(alert_state) if alert_state.last_sent.elapsed() < self.alert_interval => {
+                alert_state.suppressed_cnt += 1;
+                None
+            }
+            Some(alert_state) => {
+                let suppressed_cnt: usize = alert_state.suppressed_cnt;
+                alert_state.last_sent = Instant::now();
+                alert_state.suppressed_cnt = 0;
+                Some(suppressed_cnt)
+            }
+            None => {
+                alert_states.insert(
+                    alert_key.to_string(),
+                    AlertState {
+                        last_sent: Instant::now(),
+                        suppressed_cnt: 0,
+                    },
+                );
+                Some(0)
+            }
+        }
+    }
+
+    #[doc = "알림 간격 제한을 적용해서 Telegram 메시지를 보내주는 함수"]
+    /// # Arguments
+    /// * `alert_key` - 알림 종류를 구분하는 키
+    /// * `message` - 메시지 내용
+    async fn send_alert(&self, alert_key: &str, message: String) {
+        let telegram_client: &TelegramRepositoryPub = match get_telegram_client() {
+            Some(telegram_client) => telegram_client,
+            None => return,
+        };
+
+        let suppressed_cnt: usize = match self.check_alert_interval(alert_key).await {
+            Some(suppressed_cnt) => suppressed_cnt,
+            None => {
+                info!(
+                    "[send_alert()] '{}' was sent recently. The alert is suppressed.",
+                    alert_key
+                );
+                return;
+            }
+        };
+
+        let message: String = if suppressed_cnt > 0 {
+            format!(
+                "{}\n\n({} similar alert(s) were suppressed)",
+                message, suppressed_cnt
+            )
+        } else {
+            message
+        };
+
+        if let Err(e) = telegram_client.send_message(&message).await {
+            error!("[Error][send_alert()] {:?}", e);
+        }
+    }
+}
+
+#[async_trait]
+impl NotificationService for NotificationServicePub {
+    #[doc = "스케쥴 실행이 재시도 후에도 최종 실패한 경우 알림을 보내주는 함수"]
+    /// # Arguments
+    /// * `index_schedule` - 인덱스 스케쥴 객체
+    /// * `attempt` - 마지막 시도 횟수
+    /// * `error_class` - 실패 원인 분류
+    /// * `error` - 색인 작업 에러
+    async fn notify_run_failure(
+        &self,
+        index_schedule: &IndexSchedules,
+        attempt: u32,
+        error_class: RunErrorClass,
+        error: &anyhow::Error,
+    ) {
+        let alert_key: String = format!(
+            "run_failure:{}:{}",
+            index_schedule.index_name(),
+            index_schedule.function_name()
+        );
+
+        /* 분류된 에러인 경우 원인(404/429, bulk 실패, DB 등)을 한 줄로 알려준다. */
+        let cause: String = match find_indexing_error(error) {
+            Some(indexing_error) => indexing_error.summary(),
+            None => "unclassified".to_string(),
+        };
+
+        let message: String = format!(
+            "[yummy-indexing] Indexing failed\nindex: {} ({})\nattempts: {}\nerror class: {}\ncause: {}\n\n{}",
+            index_schedule.index_name(),
+            index_schedule.function_name(),
+            attempt,
+            error_class,
+            cause,
+            truncate_error_message(error)
+        );
+
+        self.send_alert(&alert_key, message).await;
+    }
+
+    #[doc = "정적색인 인덱스가 검증을 통과하지 못해 alias 교체가 막힌 경우 알림을 보내주는 함수"]
+    /// # Arguments
+    /// * `index_schedule` - 인덱스 스케쥴 객체
+    /// * `error` - 검증 에러
+    async fn notify_validation_blocked(
+        &self,
+        index_schedule: &IndexSchedules,
+        error: &anyhow::Error,
+    ) {
+        let alert_key: String = format!(
+            "validation_blocked:{}:{}",
+            index_schedule.index_name(),
+            index_schedule.function_name()
+        );
+
+        let message: String = format!(
+            "[yummy-indexing] Static rebuild blocked by validation\nindex: {} ({})\nThe alias still points to the previous index.\n\n{}",
+            index_schedule.index_name(),
+            index_schedule.function_name(),
+            truncate_error_message(error)
+        );
+
+        self.send_alert(&alert_key, message).await;
+    }
+
+    #[doc = "증분색인의 마지막 색인 시각(watermark)이 허용치보다 뒤쳐진 경우 알림을 보내주는 함수"]
+    /// # Arguments
+    /// * `index_schedule` - 인덱스 스케쥴 객체
+    /// * `watermark` - 마지막 색인 시각 (UTC)
+    /// * `lag_secs` - 현재 시각과의 차이(초)
+    async fn notify_watermark_lag(
+        &self,
+        index_schedule: &IndexSchedules,
+        watermark: NaiveDateTime,
+        lag_secs: i64,
+    ) {
+        let alert_key: String = format!("watermark_lag:{}", index_schedule.index_name());
+
+        let message: String = format!(
+            "[yummy-indexing] Dynamic index is falling behind\nindex: {} ({})\nwatermark: {} (UTC)\nlag: {}s (threshold: {}s)",
+            index_schedule.index_name(),
+            index_schedule.function_name(),
+            get_str_from_naive_datetime(watermark),
+            lag_secs,
+            index_schedule.watermark_lag_alert_secs().unwrap_or_default()
+        );
+
+        self.send_alert(&alert_key, message).await;
+    }
+
+    #[doc = "인덱스별 문서 수 일일 요약을 보내주는 함수 -> 하루 한번이므로 간격 제한을 적용하지 않는다."]
+    /// # Arguments
+    /// * `summary_time` - 요약 기준 시각
+    /// * `doc_counts` - (인덱스(alias) 이름, 문서 수 조회 결과) 목록
+    async fn notify_daily_summary(
+        &self,
+        summary_time: &str,
+        doc_counts: &[(String, Result<u64, anyhow::Error>)],
+    ) {
+        let telegram_client: &TelegramRepositoryPub = match get_telegram_client() {
+            Some(telegram_client) => telegram_client,
+            None => return,
+        };
+
+        let mut message: String = format!("[yummy-indexing] Daily summary ({})", summary_time);
+
+        for (index_name, doc_count) in doc_counts {
+            match doc_count {
+                Ok(doc_count) => {
+                    message.push_str(&format!("\n- {}: {} docs", index_name, doc_count))
+                }
+                Err(e) => message.push_str(&format!("\n- {}: failed to count ({})", index_name, e)),
+            }
+        }
+
+        if let Err(e) = telegram_client.send_message(&message).await {
+            error!("[Error][notify_daily_summary()] {:?}", e);
+        }
+    }
+}
+
+#[doc = "같은 알림을 다시 보내기까지의 최소 간격을 가져와주는 함수 -> 'TELEGRAM_ALERT_INTERVAL_SECS'"]
+/// # Returns
+/// * Duration
+fn get_alert_interval() -> Duration {
+    let alert_interval_secs: u64 = env::var("TELEGRAM_ALERT_INTERVAL_SECS")
+        .ok()
+        .and_then(|alert_interval_secs| alert_interval_secs.parse::<u64>().ok())
+        .unwrap_or(DEFAULT_ALERT_INTERVAL_SECS);
+
+    Duration::from_secs(alert_interval_secs)
+}
+
+#[doc = "에러 내용을 Telegram 메시지에 담을 수 있는 길이로 잘라주는 함수"]
+/// # Arguments
+/// * `error` - 에러
+///
+/// # Returns
+/// * String
+fn truncate_error_message(error: &anyhow::Error) -> String {
+    let error_message: String = format!("{:#}", error);
+
+    match error_message.char_indices().nth(MAX_ERROR_MESSAGE_LEN) {
+        Some((byte_idx, _)) => format!("{}...", &error_message[..byte_idx]),
+        None => error_message,
+    }
+}
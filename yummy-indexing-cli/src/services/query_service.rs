@@ -2,176 +2,283 @@ use crate::common::*;
 
 use crate::configuration::index_schedules_config::*;
 
+use crate::models::indexing_error::*;
+use crate::models::run_error::*;
+use crate::models::run_report::*;
 use crate::models::store_to_elastic::*;
 use crate::models::store_types::*;
 
 use crate::repository::mysql_repository::*;
 
+use crate::utils_module::metrics_utils::*;
 use crate::utils_module::time_utils::*;
 
 use crate::entity::{
-    elastic_index_info_tbl, recommend_tbl, store, store_location_info_tbl, store_recommend_tbl,
-    store_type_link_tbl, store_type_major, store_type_sub, zero_possible_market,
+    elastic_index_info_tbl, elastic_index_lock_tbl, elastic_index_run_history_tbl, recommend_tbl,
+    store, store_location_info_tbl, store_recommend_tbl, store_type_link_tbl, store_type_major,
+    store_type_sub, zero_possible_market,
 };
 
-pub trait QueryService {
+#[async_trait]
+pub trait QueryService: Send + Sync {
     async fn get_store_by_batch(
         &self,
         batch_size: usize,
         query_filter: Condition,
         cur_utc_date: NaiveDateTime,
-    ) -> Result<Vec<StoreResult>, anyhow::Error>;
+        last_seq: Option<i32>,
+    ) -> Result<Vec<StoreResult>, IndexingError>;
     async fn get_all_store_table(
         &self,
         index_schedule: &IndexSchedules,
         cur_utc_date: NaiveDateTime,
-    ) -> Result<Vec<DistinctStoreResult>, anyhow::Error>;
+        last_seq: Option<i32>,
+    ) -> Result<Vec<DistinctStoreResult>, IndexingError>;
     async fn get_specific_store_table(
         &self,
         index_schedule: &IndexSchedules,
         cur_utc_date: NaiveDateTime,
         recent_datetime: NaiveDateTime,
-    ) -> Result<Vec<DistinctStoreResult>, anyhow::Error>;
+        last_seq: Option<i32>,
+    ) -> Result<Vec<DistinctStoreResult>, IndexingError>;
     fn get_distinct_store_table(
         &self,
         stores: &[StoreResult],
         cur_utc_date: NaiveDateTime,
-    ) -> Result<Vec<DistinctStoreResult>, anyhow::Error>;
+    ) -> Result<Vec<DistinctStoreResult>, IndexingError>;
     async fn get_recent_date_from_elastic_index_info(
         &self,
         index_schedule: &IndexSchedules,
-    ) -> Result<NaiveDateTime, anyhow::Error>;
+    ) -> Result<NaiveDateTime, IndexingError>;
     async fn update_recent_date_to_elastic_index_info(
         &self,
         index_schedule: &IndexSchedules,
         new_datetime: NaiveDateTime,
-    ) -> Result<(), anyhow::Error>;
+    ) -> Result<(), IndexingError>;
     async fn get_store_types(
         &self,
         store_seqs: Option<Vec<i32>>,
-    ) -> Result<StoreTypesMap, anyhow::Error>;
+    ) -> Result<StoreTypesMap, IndexingError>;
+    async fn get_removed_store_seqs(
+        &self,
+        recent_datetime: NaiveDateTime,
+    ) -> Result<Vec<i32>, IndexingError>;
+    async fn try_acquire_index_lock(
+        &self,
+        index_name: &str,
+        lock_owner: &str,
+        lease_secs: u64,
+    ) -> Result<bool, IndexingError>;
+    async fn renew_index_lock(
+        &self,
+        index_name: &str,
+        lock_owner: &str,
+        lease_secs: u64,
+    ) -> Result<bool, IndexingError>;
+    async fn release_index_lock(
+        &self,
+        index_name: &str,
+        lock_owner: &str,
+    ) -> Result<(), IndexingError>;
+    async fn get_index_lock(
+        &self,
+        index_name: &str,
+    ) -> Result<Option<elastic_index_lock_tbl::Model>, IndexingError>;
+    async fn insert_run_history(
+        &self,
+        index_schedule: &IndexSchedules,
+        run_trigger: RunTrigger,
+        attempt: u32,
+        start_dt: NaiveDateTime,
+    ) -> Result<i64, IndexingError>;
+    async fn finish_run_history(
+        &self,
+        run_id: i64,
+        run_report: &RunReport,
+        run_status: &str,
+        end_dt: NaiveDateTime,
+        error_class: Option<RunErrorClass>,
+        error_message: Option<String>,
+    ) -> Result<(), IndexingError>;
+    async fn get_run_histories(
+        &self,
+        index_name: &str,
+        limit: u64,
+    ) -> Result<Vec<elastic_index_run_history_tbl::Model>, IndexingError>;
+    async fn get_last_successful_run(
+        &self,
+        index_name: &str,
+        function_name: &str,
+    ) -> Result<Option<elastic_index_run_history_tbl::Model>, IndexingError>;
 }
 
 #[derive(Debug, new)]
 pub struct QueryServicePub;
 
+impl QueryServicePub {
+    #[doc = "store 색인 대상 조회 쿼리를 만들어주는 함수 -> 상점 하나가 추천 정보 수만큼 여러 행으로 조회된다."]
+    /// # Arguments
+    /// * `query_filter` - 쿼리 필터
+    /// * `cur_utc_date` - 현재 시각
+    ///
+    /// # Returns
+    /// * Select<store::Entity>
+    fn store_select_query(
+        query_filter: Condition,
+        cur_utc_date: NaiveDateTime,
+    ) -> Select<store::Entity> {
+        store::Entity::find()
+            .inner_join(store_type_link_tbl::Entity)
+            .inner_join(store_location_info_tbl::Entity)
+            .left_join(zero_possible_market::Entity)
+            .left_join(store_recommend_tbl::Entity)
+            .join(
+                JoinType::LeftJoin,
+                store_recommend_tbl::Relation::RecommendTbl
+                    .def()
+                    .on_condition(move |_r, _| {
+                        Condition::all()
+                            .add(Expr::col(recommend_tbl::Column::RecommendYn).eq("Y"))
+                            .add(
+                                Expr::col(store_recommend_tbl::Column::RecommendEndDt)
+                                    .gt(cur_utc_date),
+                            )
+                    }),
+            )
+            .order_by_asc(store::Column::Seq)
+            .select_only()
+            .columns([store::Column::Seq, store::Column::Name, store::Column::Type])
+            .expr_as(
+                Expr::case(
+                    Expr::col((
+                        zero_possible_market::Entity,
+                        zero_possible_market::Column::UseYn,
+                    ))
+                    .eq("N"),
+                    false,
+                )
+                .case(
+                    Expr::col((
+                        zero_possible_market::Entity,
+                        zero_possible_market::Column::Name,
+                    ))
+                    .is_not_null(),
+                    true,
+                )
+                .finally(false),
+                "zero_possible",
+            )
+            .column_as(store_location_info_tbl::Column::Address, "address")
+            .column_as(store_location_info_tbl::Column::Lat, "lat")
+            .column_as(store_location_info_tbl::Column::Lng, "lng")
+            .column_as(recommend_tbl::Column::RecommendName, "recommend_name")
+            .column_as(
+                store_location_info_tbl::Column::LocationCity,
+                "location_city",
+            )
+            .column_as(
+                store_location_info_tbl::Column::LocationCounty,
+                "location_county",
+            )
+            .column_as(
+                store_location_info_tbl::Column::LocationDistrict,
+                "location_district",
+            )
+            .filter(query_filter)
+    }
+
+    #[doc = "lock 만료시각을 DB 시간 기준으로 계산해주는 표현식 -> 인스턴스 간 시계 차이의 영향을 받지 않는다."]
+    /// # Arguments
+    /// * `lease_secs` - lease 시간(초)
+    ///
+    /// # Returns
+    /// * SimpleExpr
+    fn lock_expire_expr(lease_secs: u64) -> SimpleExpr {
+        Expr::cust_with_values("DATE_ADD(UTC_TIMESTAMP(), INTERVAL ? SECOND)", [lease_secs])
+    }
+}
+
+#[async_trait]
 impl QueryService for QueryServicePub {
-    #[doc = "store 색인 관련 배치 함수"]
+    #[doc = "store 색인 관련 배치 함수 -> `last_seq` 이후의 상점들을 한 페이지만큼 가져와준다."]
     /// # Arguments
     /// * `batch_size` - 쿼리 배치 사이즈
     /// * `query_filter` - 쿼리 필터
     /// * `cur_utc_date` - 현재 시각
+    /// * `last_seq` - 이전 페이지의 마지막 상점 고유번호 (None 인 경우 처음부터 조회)
     ///
     /// # Returns
-    /// * Result<Vec<StoreResult>, anyhow::Error>
+    /// * Result<Vec<StoreResult>, IndexingError> - 모든 행을 가진 상점들만 담긴 페이지 (비어있으면 마지막)
     async fn get_store_by_batch(
         &self,
         batch_size: usize,
         query_filter: Condition,
         cur_utc_date: NaiveDateTime,
-    ) -> Result<Vec<StoreResult>, anyhow::Error> {
+        last_seq: Option<i32>,
+    ) -> Result<Vec<StoreResult>, IndexingError> {
         let db: &DatabaseConnection = establish_connection().await;
 
-        let mut total_store_list: Vec<StoreResult> = Vec::new();
-        let mut last_seq: Option<i32> = None;
-
-        loop {
-            let mut query: Select<store::Entity> = store::Entity::find()
-                .inner_join(store_type_link_tbl::Entity)
-                .inner_join(store_location_info_tbl::Entity)
-                .left_join(zero_possible_market::Entity)
-                .left_join(store_recommend_tbl::Entity)
-                .join(
-                    JoinType::LeftJoin,
-                    store_recommend_tbl::Relation::RecommendTbl
-                        .def()
-                        .on_condition(move |_r, _| {
-                            Condition::all()
-                                .add(Expr::col(recommend_tbl::Column::RecommendYn).eq("Y"))
-                                .add(
-                                    Expr::col(store_recommend_tbl::Column::RecommendEndDt)
-                                        .gt(cur_utc_date),
-                                )
-                        }),
-                )
-                .order_by_asc(store::Column::Seq)
-                .limit(batch_size as u64)
-                .select_only()
-                .columns([store::Column::Seq, store::Column::Name, store::Column::Type])
-                .expr_as(
-                    Expr::case(
-                        Expr::col((
-                            zero_possible_market::Entity,
-                            zero_possible_market::Column::UseYn,
-                        ))
-                        .eq("N"),
-                        false,
-                    )
-                    .case(
-                        Expr::col((
-                            zero_possible_market::Entity,
-                            zero_possible_market::Column::Name,
-                        ))
-                        .is_not_null(),
-                        true,
-                    )
-                    .finally(false),
-                    "zero_possible",
-                )
-                .column_as(store_location_info_tbl::Column::Address, "address")
-                .column_as(store_location_info_tbl::Column::Lat, "lat")
-                .column_as(store_location_info_tbl::Column::Lng, "lng")
-                .column_as(recommend_tbl::Column::RecommendName, "recommend_name")
-                .column_as(
-                    store_location_info_tbl::Column::LocationCity,
-                    "location_city",
-                )
-                .column_as(
-                    store_location_info_tbl::Column::LocationCounty,
-                    "location_county",
-                )
-                .column_as(
-                    store_location_info_tbl::Column::LocationDistrict,
-                    "location_district",
-                )
-                .filter(query_filter.clone());
+        let mut query: Select<store::Entity> =
+            Self::store_select_query(query_filter.clone(), cur_utc_date).limit(batch_size as u64);
 
-            if let Some(seq) = last_seq {
-                query = query.filter(store::Column::Seq.gt(seq)); /* `seq`가 마지막 값보다 큰 데이터 가져오기 */
-            }
+        if let Some(seq) = last_seq {
+            query = query.filter(store::Column::Seq.gt(seq)); /* `seq`가 마지막 값보다 큰 데이터 가져오기 */
+        }
 
-            let mut store_results: Vec<StoreResult> = query.into_model().all(db).await?;
+        let mut store_results: Vec<StoreResult> = query.into_model().all(db).await?;
 
-            if store_results.is_empty() {
-                break;
-            }
+        /* 페이지가 가득 차지 않았다면 마지막 페이지이므로 잘린 상점이 없다. */
+        if store_results.len() < batch_size {
+            return Ok(store_results);
+        }
+
+        /*
+            페이지가 가득 찬 경우 마지막 상점의 추천 정보 행들이 다음 페이지로 넘어갔을 수 있다.
+            마지막 상점의 행들은 이번 페이지에서 제외하여 다음 페이지에서 한번에 조회되도록 해준다.
+        */
+        let trailing_seq: i32 = match store_results.last() {
+            Some(store) => store.seq,
+            None => return Ok(store_results),
+        };
+
+        let trailing_start: usize = store_results.partition_point(|store| store.seq < trailing_seq);
 
-            total_store_list.append(&mut store_results);
-            last_seq = total_store_list.last().map(|s| s.seq);
+        if trailing_start > 0 {
+            store_results.truncate(trailing_start);
+            return Ok(store_results);
         }
 
-        Ok(total_store_list)
+        /* 페이지 전체가 하나의 상점인 경우에는 해당 상점의 모든 행을 따로 조회해준다. */
+        let trailing_store: Vec<StoreResult> = Self::store_select_query(query_filter, cur_utc_date)
+            .filter(store::Column::Seq.eq(trailing_seq))
+            .into_model()
+            .all(db)
+            .await?;
+
+        Ok(trailing_store)
     }
 
-    #[doc = "색인할 Store 정보를 조회해주는 함수 -> 모든 정보를 가져와준다: 정적색인 용도"]
+    #[doc = "색인할 Store 정보를 페이지 단위로 조회해주는 함수 -> 모든 정보를 가져와준다: 정적색인 용도"]
     /// # Arguments
     /// * `index_schedule` - index_schedule 정보
     /// * `cur_utc_date` - 현재 시각
+    /// * `last_seq` - 이전 페이지의 마지막 상점 고유번호
     ///
     /// # Returns
-    /// * Result<Vec<DistinctStoreResult>, anyhow::Error>
+    /// * Result<Vec<DistinctStoreResult>, IndexingError> - 비어있으면 마지막 페이지
     async fn get_all_store_table(
         &self,
         index_schedule: &IndexSchedules,
         cur_utc_date: NaiveDateTime,
-    ) -> Result<Vec<DistinctStoreResult>, anyhow::Error> {
-        let batch_size: usize = *index_schedule.es_batch_size();
+        last_seq: Option<i32>,
+    ) -> Result<Vec<DistinctStoreResult>, IndexingError> {
+        let batch_size: usize = *index_schedule.sql_batch_size();
         let query_filter: Condition =
             Condition::all().add(Expr::col((store::Entity, store::Column::UseYn)).eq("Y"));
 
         /* 중복이 존재하는 store 리스트 */
         let stores: Vec<StoreResult> = self
-            .get_store_by_batch(batch_size, query_filter, cur_utc_date)
+            .get_store_by_batch(batch_size, query_filter, cur_utc_date, last_seq)
             .await?;
 
         /* 중복을 제외한 store 리스트 */
@@ -181,23 +288,31 @@ impl QueryService for QueryServicePub {
         Ok(stores_distinct)
     }
 
-    #[doc = "색인할 Store 정보를 조회해주는 함수 -> 특정 정보를 가져와준다: 증분색인 용도"]
+    #[doc = "색인할 Store 정보를 페이지 단위로 조회해주는 함수 -> 특정 정보를 가져와준다: 증분색인 용도"]
     /// # Arguments
     /// * `index_schedule` - index_schedule 정보
     /// * `cur_utc_date` - 현재 시각정보
     /// * `recent_datetime` - 가장 최근 색인 시각정보
+    /// * `last_seq` - 이전 페이지의 마지막 상점 고유번호
     ///
     /// # Returns
-    /// * Result<Vec<DistinctStoreResult>, anyhow::Error>
+    /// * Result<Vec<DistinctStoreResult>, IndexingError> - 비어있으면 마지막 페이지
     async fn get_specific_store_table(
         &self,
         index_schedule: &IndexSchedules,
         cur_utc_date: NaiveDateTime,
         recent_datetime: NaiveDateTime,
-    ) -> Result<Vec<DistinctStoreResult>, anyhow::Error> {
-        let batch_size: usize = *index_schedule.es_batch_size();
+        last_seq: Option<i32>,
+    ) -> Result<Vec<DistinctStoreResult>, IndexingError> {
+        let batch_size: usize = *index_schedule.sql_batch_size();
+        let db: &DatabaseConnection = establish_connection().await;
 
-        let query_filter: Condition = Condition::all()
+        /*
+            1. 마지막 색인 이후 변경된 상점의 고유번호만 먼저 페이지 단위로 가져온다.
+            변경된 행만 조회하면 바뀌지 않은 추천 정보 등이 빠진 채로 문서 전체가 덮어써지므로,
+            변경 여부는 상점 단위로만 판단한다.
+        */
+        let changed_filter: Condition = Condition::all()
             .add(Expr::col((store::Entity, store::Column::UseYn)).eq("Y"))
             .add(
                 Condition::any()
@@ -261,9 +376,37 @@ impl QueryService for QueryServicePub {
                     ),
             );
 
+        let mut changed_query: Select<store::Entity> = store::Entity::find()
+            .inner_join(store_type_link_tbl::Entity)
+            .inner_join(store_location_info_tbl::Entity)
+            .left_join(zero_possible_market::Entity)
+            .left_join(store_recommend_tbl::Entity)
+            .select_only()
+            .column(store::Column::Seq)
+            .distinct()
+            .filter(changed_filter)
+            .order_by_asc(store::Column::Seq)
+            .limit(batch_size as u64);
+
+        if let Some(seq) = last_seq {
+            changed_query = changed_query.filter(store::Column::Seq.gt(seq)); /* `seq`가 마지막 값보다 큰 데이터 가져오기 */
+        }
+
+        let changed_seqs: Vec<i32> = changed_query.into_tuple::<i32>().all(db).await?;
+
+        if changed_seqs.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        /* 2. 변경된 상점들의 모든 행을 가져와서 문서 전체를 다시 만들어준다. */
+        let query_filter: Condition = Condition::all()
+            .add(Expr::col((store::Entity, store::Column::UseYn)).eq("Y"))
+            .add(Expr::col((store::Entity, store::Column::Seq)).is_in(changed_seqs));
+
         /* 중복이 존재하는 store 리스트 */
-        let stores: Vec<StoreResult> = self
-            .get_store_by_batch(batch_size, query_filter, cur_utc_date)
+        let stores: Vec<StoreResult> = Self::store_select_query(query_filter, cur_utc_date)
+            .into_model()
+            .all(db)
             .await?;
 
         /* 중복을 제외한 store 리스트 */
@@ -279,16 +422,25 @@ impl QueryService for QueryServicePub {
     /// * `cur_utc_date` - 현재 UTC 기준 시간 데이터
     ///
     /// # Returns
-    /// * Result<Vec<DistinctStoreResult>, anyhow::Error>
+    /// * Result<Vec<DistinctStoreResult>, IndexingError>
     fn get_distinct_store_table(
         &self,
         stores: &[StoreResult],
         cur_utc_date: NaiveDateTime,
-    ) -> Result<Vec<DistinctStoreResult>, anyhow::Error> {
+    ) -> Result<Vec<DistinctStoreResult>, IndexingError> {
         let mut store_map: HashMap<i32, DistinctStoreResult> = HashMap::new();
         let cur_time_utc: String = get_str_from_naive_datetime(cur_utc_date);
 
         for store in stores {
+            let location: Option<GeoPoint> = GeoPoint::from_decimal(store.lat, store.lng);
+
+            if location.is_none() && !store_map.contains_key(&store.seq) {
+                warn!(
+                    "[get_distinct_store_table()] Invalid coordinates, the store is indexed without 'location'. seq: {}, lat: {}, lng: {}",
+                    store.seq, store.lat, store.lng
+                );
+            }
+
             store_map
                 .entry(store.seq)
                 .and_modify(|existing| {
@@ -297,26 +449,13 @@ impl QueryService for QueryServicePub {
                     }
                 })
                 .or_insert_with(|| {
-                    DistinctStoreResult::new(
-                        cur_time_utc.clone(),
-                        store.seq,
-                        store.name.clone(),
-                        store.r#type.clone(),
-                        store.address.clone(),
-                        store.lat,
-                        store.lng,
-                        store.zero_possible,
-                        store.recommend_name.clone().map_or(vec![], |r| vec![r]),
-                        store.location_city.clone(),
-                        store.location_county.clone(),
-                        store.location_district.clone(),
-                        Vec::new(),
-                        Vec::new(),
-                    )
+                    DistinctStoreResult::from_store_result(store, cur_time_utc.clone(), location)
                 });
         }
 
-        let result: Vec<DistinctStoreResult> = store_map.into_values().collect();
+        let mut result: Vec<DistinctStoreResult> = store_map.into_values().collect();
+        result.sort_by_key(|store| store.seq);
+
         Ok(result)
     }
 
@@ -325,11 +464,11 @@ impl QueryService for QueryServicePub {
     /// * `index_schedule` - 인덱스 스케쥴 정보
     ///
     /// # Returns
-    /// * Result<NaiveDateTime, anyhow::Error>
+    /// * Result<NaiveDateTime, IndexingError>
     async fn get_recent_date_from_elastic_index_info(
         &self,
         index_schedule: &IndexSchedules,
-    ) -> Result<NaiveDateTime, anyhow::Error> {
+    ) -> Result<NaiveDateTime, IndexingError> {
         let index_name: &String = index_schedule.index_name();
 
         let db: &DatabaseConnection = establish_connection().await;
@@ -339,16 +478,18 @@ impl QueryService for QueryServicePub {
 
         let query_results: Vec<elastic_index_info_tbl::Model> = query.all(db).await?;
 
-        if query_results.is_empty() {
-            return Err(anyhow!(
-                "[Error][get_recent_date_from_elastic_index_info()] query_results is EMPTY"
-            ));
-        }
+        /* 색인 시각을 기록할 행이 없으면 인덱스 등록이 누락된 설정 문제로 본다. */
+        let recent_datetime: NaiveDateTime = match query_results.first() {
+            Some(index_info) => index_info.chg_dt,
+            None => {
+                return Err(IndexingError::Config(format!(
+                    "[get_recent_date_from_elastic_index_info()] '{}' is not registered in 'elastic_index_info'.",
+                    index_name
+                )))
+            }
+        };
 
-        let recent_datetime: NaiveDateTime = query_results
-            .get(0)
-            .ok_or_else(|| anyhow!("[Error][get_recent_date_from_elastic_index_info()] The first element of 'query_results' does not exist."))?
-            .chg_dt;
+        get_indexing_metrics().record_watermark(index_name, recent_datetime);
 
         Ok(recent_datetime)
     }
@@ -359,12 +500,12 @@ impl QueryService for QueryServicePub {
     /// * `new_datetime` - 새로운 날짜/시간 데이터
     ///
     /// # Returns
-    /// * Result<NaiveDateTime, anyhow::Error>
+    /// * Result<NaiveDateTime, IndexingError>
     async fn update_recent_date_to_elastic_index_info(
         &self,
         index_schedule: &IndexSchedules,
         new_datetime: NaiveDateTime,
-    ) -> Result<(), anyhow::Error> {
+    ) -> Result<(), IndexingError> {
         let index_name: &String = index_schedule.index_name();
 
         let db: &DatabaseConnection = establish_connection().await;
@@ -378,6 +519,8 @@ impl QueryService for QueryServicePub {
             .exec(db)
             .await?;
 
+        get_indexing_metrics().record_watermark(index_name, new_datetime);
+
         Ok(())
     }
 
@@ -386,11 +529,11 @@ impl QueryService for QueryServicePub {
     /// * `store_seqs` - 상점 고유번호 리스트
     ///
     /// # Returns
-    /// * Result<StoreTypesMap, anyhow::Error>
+    /// * Result<StoreTypesMap, IndexingError>
     async fn get_store_types(
         &self,
         store_seqs: Option<Vec<i32>>,
-    ) -> Result<StoreTypesMap, anyhow::Error> {
+    ) -> Result<StoreTypesMap, IndexingError> {
         let db: &DatabaseConnection = establish_connection().await;
 
         let query_filter: Condition = if let Some(seqs) = store_seqs {
@@ -413,48 +556,411 @@ impl QueryService for QueryServicePub {
             .columns([store::Column::Seq])
             .column_as(store_type_sub::Column::SubType, "sub_type")
             .column_as(store_type_major::Column::MajorType, "major_type")
+            .column_as(store_type_sub::Column::TypeName, "sub_type_name")
+            .column_as(store_type_major::Column::TypeName, "major_type_name")
             .filter(query_filter);
 
         let store_types_result: Vec<StoreTypesResult> = query.into_model().all(db).await?;
 
         let mut store_type_major_map: HashMap<i32, Vec<i32>> = HashMap::new();
         let mut store_type_sub_map: HashMap<i32, Vec<i32>> = HashMap::new();
+        let mut store_type_major_name_map: HashMap<i32, Vec<String>> = HashMap::new();
+        let mut store_type_sub_name_map: HashMap<i32, Vec<String>> = HashMap::new();
 
         /* 중복체크를 위한 HashSet */
         let mut major_seen: HashMap<i32, HashSet<i32>> = HashMap::new();
         let mut sub_seen: HashMap<i32, HashSet<i32>> = HashMap::new();
 
         for store in &store_types_result {
-            store_type_major_map
-                .entry(store.seq)
-                .or_insert_with(Vec::new);
+            store_type_major_map.entry(store.seq).or_default();
+
+            store_type_major_name_map.entry(store.seq).or_default();
 
-            major_seen.entry(store.seq).or_insert_with(HashSet::new);
+            major_seen.entry(store.seq).or_default();
 
             if let Some(major_types) = store_type_major_map.get_mut(&store.seq) {
                 if let Some(major_set) = major_seen.get_mut(&store.seq) {
                     if major_set.insert(store.major_type) {
                         major_types.push(store.major_type);
+
+                        if let Some(major_names) = store_type_major_name_map.get_mut(&store.seq) {
+                            major_names.push(store.major_type_name.clone());
+                        }
                     }
                 }
             }
 
-            store_type_sub_map.entry(store.seq).or_insert_with(Vec::new);
+            store_type_sub_map.entry(store.seq).or_default();
+
+            store_type_sub_name_map.entry(store.seq).or_default();
 
-            sub_seen.entry(store.seq).or_insert_with(HashSet::new);
+            sub_seen.entry(store.seq).or_default();
 
             if let Some(sub_types) = store_type_sub_map.get_mut(&store.seq) {
                 if let Some(sub_set) = sub_seen.get_mut(&store.seq) {
                     if sub_set.insert(store.sub_type) {
                         sub_types.push(store.sub_type);
+
+                        if let Some(sub_names) = store_type_sub_name_map.get_mut(&store.seq) {
+                            sub_names.push(store.sub_type_name.clone());
+                        }
                     }
                 }
             }
         }
 
-        let store_types_map: StoreTypesMap =
-            StoreTypesMap::new(store_type_major_map, store_type_sub_map);
+        let store_types_map: StoreTypesMap = StoreTypesMap::new(
+            store_type_major_map,
+            store_type_sub_map,
+            store_type_major_name_map,
+            store_type_sub_name_map,
+        );
 
         Ok(store_types_map)
     }
+
+    #[doc = "마지막 색인 이후 비활성화(use_yn = 'N') 된 음식점의 고유번호를 가져와 준다."]
+    /// # Arguments
+    /// * `recent_datetime` - 가장 최근 색인 시각정보
+    ///
+    /// # Returns
+    /// * Result<Vec<i32>, IndexingError>
+    async fn get_removed_store_seqs(
+        &self,
+        recent_datetime: NaiveDateTime,
+    ) -> Result<Vec<i32>, IndexingError> {
+        let db: &DatabaseConnection = establish_connection().await;
+
+        let query: Select<store::Entity> = store::Entity::find()
+            .select_only()
+            .column(store::Column::Seq)
+            .filter(store::Column::UseYn.eq("N"))
+            .filter(store::Column::ChgDt.gt(recent_datetime))
+            .order_by_asc(store::Column::Seq);
+
+        let store_seqs: Vec<i32> = query.into_tuple::<i32>().all(db).await?;
+
+        Ok(store_seqs)
+    }
+
+    #[doc = "인덱스 lock 을 획득해주는 함수 -> 다른 인스턴스의 lock 이라도 lease 가 만료되었으면 가져온다."]
+    /// # Arguments
+    /// * `index_name` - 인덱스(alias) 이름
+    /// * `lock_owner` - lock 을 획득할 인스턴스 식별자
+    /// * `lease_secs` - lease 시간(초)
+    ///
+    /// # Returns
+    /// * Result<bool, IndexingError> - 다른 인스턴스가 lock 을 가지고 있으면 false
+    async fn try_acquire_index_lock(
+        &self,
+        index_name: &str,
+        lock_owner: &str,
+        lease_secs: u64,
+    ) -> Result<bool, IndexingError> {
+        let db: &DatabaseConnection = establish_connection().await;
+
+        /* 1. lease 가 만료되었거나 이미 내가 가진 lock 이면 가져와준다. */
+        let update_result = elastic_index_lock_tbl::Entity::update_many()
+            .col_expr(
+                elastic_index_lock_tbl::Column::LockOwner,
+                Expr::value(lock_owner),
+            )
+            .col_expr(
+                elastic_index_lock_tbl::Column::LockExpireDt,
+                Self::lock_expire_expr(lease_secs),
+            )
+            .col_expr(
+                elastic_index_lock_tbl::Column::ChgDt,
+                Expr::cust("UTC_TIMESTAMP()"),
+            )
+            .filter(elastic_index_lock_tbl::Column::IndexName.eq(index_name))
+            .filter(
+                Condition::any()
+                    .add(elastic_index_lock_tbl::Column::LockOwner.eq(lock_owner))
+                    .add(
+                        Expr::col(elastic_index_lock_tbl::Column::LockExpireDt)
+                            .lt(Expr::cust("UTC_TIMESTAMP()")),
+                    ),
+            )
+            .exec(db)
+            .await?;
+
+        if update_result.rows_affected > 0 {
+            return Ok(true);
+        }
+
+        /* 2. lock 데이터가 없으면 새로 만들어준다. -> 동시에 만들려고 하면 한쪽만 성공한다. */
+        let insert_query = Query::insert()
+            .into_table(elastic_index_lock_tbl::Entity)
+            .columns([
+                elastic_index_lock_tbl::Column::IndexName,
+                elastic_index_lock_tbl::Column::LockOwner,
+                elastic_index_lock_tbl::Column::LockExpireDt,
+                elastic_index_lock_tbl::Column::RegDt,
+                elastic_index_lock_tbl::Column::ChgDt,
+            ])
+            .values_panic([
+                index_name.into(),
+                lock_owner.into(),
+                Self::lock_expire_expr(lease_secs),
+                Expr::cust("UTC_TIMESTAMP()"),
+                Expr::cust("UTC_TIMESTAMP()"),
+            ])
+            .to_owned();
+
+        match db
+            .execute(db.get_database_backend().build(&insert_query))
+            .await
+        {
+            Ok(_) => Ok(true),
+            Err(e) => match e.sql_err() {
+                Some(SqlErr::UniqueConstraintViolation(_)) => Ok(false),
+                _ => {
+                    error!(
+                        "[Error][try_acquire_index_lock()] Failed to create the lock of '{}'.: {:?}",
+                        index_name, e
+                    );
+                    Err(IndexingError::Db(e))
+                }
+            },
+        }
+    }
+
+    #[doc = "가지고 있는 인덱스 lock 의 lease 를 연장해주는 함수 (heartbeat)"]
+    /// # Arguments
+    /// * `index_name` - 인덱스(alias) 이름
+    /// * `lock_owner` - lock 을 가진 인스턴스 식별자
+    /// * `lease_secs` - lease 시간(초)
+    ///
+    /// # Returns
+    /// * Result<bool, IndexingError> - lock 을 이미 빼앗긴 경우 false
+    async fn renew_index_lock(
+        &self,
+        index_name: &str,
+        lock_owner: &str,
+        lease_secs: u64,
+    ) -> Result<bool, IndexingError> {
+        let db: &DatabaseConnection = establish_connection().await;
+
+        let update_result = elastic_index_lock_tbl::Entity::update_many()
+            .col_expr(
+                elastic_index_lock_tbl::Column::LockExpireDt,
+                Self::lock_expire_expr(lease_secs),
+            )
+            .col_expr(
+                elastic_index_lock_tbl::Column::ChgDt,
+                Expr::cust("UTC_TIMESTAMP()"),
+            )
+            .filter(elastic_index_lock_tbl::Column::IndexName.eq(index_name))
+            .filter(elastic_index_lock_tbl::Column::LockOwner.eq(lock_owner))
+            .exec(db)
+            .await?;
+
+        Ok(update_result.rows_affected > 0)
+    }
+
+    #[doc = "가지고 있는 인덱스 lock 을 반납해주는 함수"]
+    /// # Arguments
+    /// * `index_name` - 인덱스(alias) 이름
+    /// * `lock_owner` - lock 을 가진 인스턴스 식별자
+    ///
+    /// # Returns
+    /// * Result<(), IndexingError>
+    async fn release_index_lock(
+        &self,
+        index_name: &str,
+        lock_owner: &str,
+    ) -> Result<(), IndexingError> {
+        let db: &DatabaseConnection = establish_connection().await;
+
+        elastic_index_lock_tbl::Entity::delete_many()
+            .filter(elastic_index_lock_tbl::Column::IndexName.eq(index_name))
+            .filter(elastic_index_lock_tbl::Column::LockOwner.eq(lock_owner))
+            .exec(db)
+            .await?;
+
+        Ok(())
+    }
+
+    #[doc = "인덱스 lock 정보를 가져와주는 함수"]
+    /// # Arguments
+    /// * `index_name` - 인덱스(alias) 이름
+    ///
+    /// # Returns
+    /// * Result<Option<elastic_index_lock_tbl::Model>, IndexingError>
+    async fn get_index_lock(
+        &self,
+        index_name: &str,
+    ) -> Result<Option<elastic_index_lock_tbl::Model>, IndexingError> {
+        let db: &DatabaseConnection = establish_connection().await;
+
+        let index_lock: Option<elastic_index_lock_tbl::Model> =
+            elastic_index_lock_tbl::Entity::find_by_id(index_name)
+                .one(db)
+                .await?;
+
+        Ok(index_lock)
+    }
+
+    #[doc = "색인 작업의 시작을 실행 이력 테이블에 기록해주는 함수"]
+    /// # Arguments
+    /// * `index_schedule` - 인덱스 스케쥴 정보
+    /// * `run_trigger` - 색인 작업을 실행시킨 주체
+    /// * `attempt` - 시도 횟수 (1부터 시작)
+    /// * `start_dt` - 시작 시각 (UTC)
+    ///
+    /// # Returns
+    /// * Result<i64, IndexingError> - 실행 이력 고유번호
+    async fn insert_run_history(
+        &self,
+        index_schedule: &IndexSchedules,
+        run_trigger: RunTrigger,
+        attempt: u32,
+        start_dt: NaiveDateTime,
+    ) -> Result<i64, IndexingError> {
+        let db: &DatabaseConnection = establish_connection().await;
+
+        let run_history: elastic_index_run_history_tbl::ActiveModel =
+            elastic_index_run_history_tbl::ActiveModel {
+                run_id: NotSet,
+                index_name: Set(index_schedule.index_name().clone()),
+                function_name: Set(index_schedule.function_name().clone()),
+                indexing_type: Set(index_schedule.indexing_type().clone()),
+                run_trigger: Set(run_trigger.as_str().to_string()),
+                run_status: Set(RUN_STATUS_RUNNING.to_string()),
+                attempt: Set(attempt as i32),
+                start_dt: Set(start_dt),
+                end_dt: Set(None),
+                rows_read: Set(0),
+                documents_written: Set(0),
+                documents_deleted: Set(0),
+                documents_failed: Set(0),
+                generated_index_name: Set(None),
+                error_class: Set(None),
+                error_message: Set(None),
+            };
+
+        let insert_result = elastic_index_run_history_tbl::Entity::insert(run_history)
+            .exec(db)
+            .await?;
+
+        Ok(insert_result.last_insert_id)
+    }
+
+    #[doc = "색인 작업의 결과와 통계를 실행 이력 테이블에 기록해주는 함수"]
+    /// # Arguments
+    /// * `run_id` - 실행 이력 고유번호
+    /// * `run_report` - 색인 작업 처리 결과
+    /// * `run_status` - 최종 상태
+    /// * `end_dt` - 종료 시각 (UTC)
+    /// * `error_class` - 실패한 경우 실패 원인 분류
+    /// * `error_message` - 실패한 경우 에러 내용
+    ///
+    /// # Returns
+    /// * Result<(), IndexingError>
+    async fn finish_run_history(
+        &self,
+        run_id: i64,
+        run_report: &RunReport,
+        run_status: &str,
+        end_dt: NaiveDateTime,
+        error_class: Option<RunErrorClass>,
+        error_message: Option<String>,
+    ) -> Result<(), IndexingError> {
+        let db: &DatabaseConnection = establish_connection().await;
+
+        elastic_index_run_history_tbl::Entity::update_many()
+            .col_expr(
+                elastic_index_run_history_tbl::Column::RunStatus,
+                Expr::value(run_status),
+            )
+            .col_expr(
+                elastic_index_run_history_tbl::Column::EndDt,
+                Expr::value(end_dt),
+            )
+            .col_expr(
+                elastic_index_run_history_tbl::Column::RowsRead,
+                Expr::value(run_report.rows_read as i64),
+            )
+            .col_expr(
+                elastic_index_run_history_tbl::Column::DocumentsWritten,
+                Expr::value(run_report.documents_written as i64),
+            )
+            .col_expr(
+                elastic_index_run_history_tbl::Column::DocumentsDeleted,
+                Expr::value(run_report.documents_deleted as i64),
+            )
+            .col_expr(
+                elastic_index_run_history_tbl::Column::DocumentsFailed,
+                Expr::value(run_report.documents_failed as i64),
+            )
+            .col_expr(
+                elastic_index_run_history_tbl::Column::GeneratedIndexName,
+                Expr::value(run_report.generated_index_name.clone()),
+            )
+            .col_expr(
+                elastic_index_run_history_tbl::Column::ErrorClass,
+                Expr::value(error_class.map(|error_class| error_class.as_str())),
+            )
+            .col_expr(
+                elastic_index_run_history_tbl::Column::ErrorMessage,
+                Expr::value(error_message),
+            )
+            .filter(elastic_index_run_history_tbl::Column::RunId.eq(run_id))
+            .exec(db)
+            .await?;
+
+        Ok(())
+    }
+
+    #[doc = "인덱스의 최근 실행 이력을 최신순으로 가져와주는 함수"]
+    /// # Arguments
+    /// * `index_name` - 인덱스(alias) 이름
+    /// * `limit` - 가져올 실행 이력 수
+    ///
+    /// # Returns
+    /// * Result<Vec<elastic_index_run_history_tbl::Model>, IndexingError>
+    async fn get_run_histories(
+        &self,
+        index_name: &str,
+        limit: u64,
+    ) -> Result<Vec<elastic_index_run_history_tbl::Model>, IndexingError> {
+        let db: &DatabaseConnection = establish_connection().await;
+
+        let run_histories: Vec<elastic_index_run_history_tbl::Model> =
+            elastic_index_run_history_tbl::Entity::find()
+                .filter(elastic_index_run_history_tbl::Column::IndexName.eq(index_name))
+                .order_by_desc(elastic_index_run_history_tbl::Column::RunId)
+                .limit(limit)
+                .all(db)
+                .await?;
+
+        Ok(run_histories)
+    }
+
+    #[doc = "인덱스 색인 함수의 마지막 성공 실행 이력을 가져와주는 함수"]
+    /// # Arguments
+    /// * `index_name` - 인덱스(alias) 이름
+    /// * `function_name` - 색인 함수 이름
+    ///
+    /// # Returns
+    /// * Result<Option<elastic_index_run_history_tbl::Model>, IndexingError>
+    async fn get_last_successful_run(
+        &self,
+        index_name: &str,
+        function_name: &str,
+    ) -> Result<Option<elastic_index_run_history_tbl::Model>, IndexingError> {
+        let db: &DatabaseConnection = establish_connection().await;
+
+        let last_run: Option<elastic_index_run_history_tbl::Model> =
+            elastic_index_run_history_tbl::Entity::find()
+                .filter(elastic_index_run_history_tbl::Column::IndexName.eq(index_name))
+                .filter(elastic_index_run_history_tbl::Column::FunctionName.eq(function_name))
+                .filter(elastic_index_run_history_tbl::Column::RunStatus.eq(RUN_STATUS_SUCCESS))
+                .order_by_desc(elastic_index_run_history_tbl::Column::StartDt)
+                .one(db)
+                .await?;
+
+        Ok(last_run)
+    }
 }
@@ -41,3 +41,61 @@ pub fn read_toml_from_file<T: DeserializeOwned>(file_path: &str) -> Result<T, an
 
     Ok(toml)
 }
+
+#[doc = "json 객체들을 NDJSON 형식으로 파일 끝에 이어서 써주는 함수"]
+/// # Arguments
+/// * `file_path` - 기록할 대상 파일 경로 (상위 디렉토리가 없으면 생성해준다.)
+/// * `rows` - 한 줄씩 기록할 json 객체 리스트
+///
+/// # Returns
+/// * Result<(), anyhow::Error>
+pub fn append_ndjson_to_file(file_path: &str, rows: &[Value]) -> Result<(), anyhow::Error> {
+    if let Some(parent_dir) = std::path::Path::new(file_path).parent() {
+        std::fs::create_dir_all(parent_dir)?;
+    }
+
+    let mut file: File = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(file_path)?;
+
+    for row in rows {
+        writeln!(file, "{}", serde_json::to_string(row)?)?;
+    }
+
+    Ok(())
+}
+
+#[doc = "표준출력에 안내문구를 출력한 뒤, 사용자 입력 한 줄을 읽어주는 함수"]
+/// # Arguments
+/// * `prompt` - 입력을 받기 전에 출력할 안내문구
+///
+/// # Returns
+/// * Result<String, anyhow::Error> - 앞뒤 공백이 제거된 입력값
+pub fn read_user_input(prompt: &str) -> Result<String, anyhow::Error> {
+    let mut stdout: io::Stdout = io::stdout();
+    write!(stdout, "{}", prompt)?;
+    stdout.flush()?; /* 즉시출력 */
+
+    let mut input: String = String::new();
+    io::stdin().read_line(&mut input)?;
+
+    Ok(input.trim().to_string())
+}
+
+#[doc = "사용자에게 y/N 확인을 받아주는 함수"]
+/// # Arguments
+/// * `prompt` - 확인 안내문구
+/// * `assume_yes` - true 인 경우 입력을 받지 않고 바로 승인한다. (--yes)
+///
+/// # Returns
+/// * Result<bool, anyhow::Error>
+pub fn confirm_user_input(prompt: &str, assume_yes: bool) -> Result<bool, anyhow::Error> {
+    if assume_yes {
+        return Ok(true);
+    }
+
+    let input: String = read_user_input(prompt)?;
+
+    Ok(input.eq_ignore_ascii_case("y") || input.eq_ignore_ascii_case("yes"))
+}
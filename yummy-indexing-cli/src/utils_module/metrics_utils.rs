@@ -0,0 +1,384 @@
+use crate::common::*;
+
+use crate::models::run_error::*;
+use crate::models::run_report::*;
+
+#[doc = "색인 프로그램의 Prometheus 지표를 전역으로 공유해주는 변수"]
+static INDEXING_METRICS: once_lazy<IndexingMetrics> =
+    once_lazy::new(|| match IndexingMetrics::new() {
+        Ok(indexing_metrics) => indexing_metrics,
+        Err(e) => {
+            error!("[Error][IndexingMetrics::new()] {:?}", e);
+            panic!("[Error][IndexingMetrics::new()] {:?}", e);
+        }
+    });
+
+#[doc = "색인 작업 소요시간 histogram 의 구간(초) -> 증분색인(수 초) 부터 정적색인(수십 분) 까지"]
+const RUN_DURATION_BUCKETS: [f64; 11] = [
+    1.0, 5.0, 15.0, 30.0, 60.0, 120.0, 300.0, 600.0, 1200.0, 1800.0, 3600.0,
+];
+
+#[doc = "bulk 요청 소요시간 histogram 의 구간(초)"]
+const BULK_DURATION_BUCKETS: [f64; 10] = [0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0, 60.0];
+
+#[doc = "색인 프로그램의 Prometheus 지표 모음"]
+#[derive(Debug)]
+pub struct IndexingMetrics {
+    registry: Registry,
+    runs_started: IntCounterVec,
+    runs_succeeded: IntCounterVec,
+    runs_failed: IntCounterVec,
+    run_duration: HistogramVec,
+    documents_read: IntCounterVec,
+    documents_written: IntCounterVec,
+    documents_deleted: IntCounterVec,
+    documents_failed: IntCounterVec,
+    bulk_request_duration: HistogramVec,
+    bulk_errors: IntCounterVec,
+    es_pool_size: IntGauge,
+    es_pool_idle: IntGauge,
+    es_pool_in_use: IntGauge,
+    watermark_age: IntGaugeVec,
+    es_node_healthy: IntGaugeVec,
+    es_node_state_changes: IntCounterVec,
+    /* 인덱스별 마지막 색인 시각 (UTC) -> 지표를 수집할 때 현재 시각과의 차이를 계산한다. */
+    watermarks: std::sync::Mutex<HashMap<String, NaiveDateTime>>,
+}
+
+impl IndexingMetrics {
+    #[doc = "지표들을 만들어 registry 에 등록해주는 함수"]
+    /// # Returns
+    /// * Result<IndexingMetrics, anyhow::Error>
+    fn new() -> Result<Self, anyhow::Error> {
+        let registry: Registry = Registry::new_custom(Some("yummy_indexing".to_string()), None)?;
+
+        let run_labels: [&str; 2] = ["index", "function"];
+
+        let runs_started: IntCounterVec = IntCounterVec::new(
+            Opts::new("runs_started_total", "Number of indexing runs started"),
+            &run_labels,
+        )?;
+        let runs_succeeded: IntCounterVec = IntCounterVec::new(
+            Opts::new("runs_succeeded_total", "Number of indexing runs succeeded"),
+            &run_labels,
+        )?;
+        let runs_failed: IntCounterVec = IntCounterVec::new(
+            Opts::new("runs_failed_total", "Number of indexing runs failed"),
+            &["index", "function", "error_class"],
+        )?;
+        let run_duration: HistogramVec = HistogramVec::new(
+            HistogramOpts::new("run_duration_seconds", "Duration of indexing runs")
+                .buckets(RUN_DURATION_BUCKETS.to_vec()),
+            &["index", "function", "status"],
+        )?;
+        let documents_read: IntCounterVec = IntCounterVec::new(
+            Opts::new("documents_read_total", "Number of rows read from MySQL"),
+            &run_labels,
+        )?;
+        let documents_written: IntCounterVec = IntCounterVec::new(
+            Opts::new(
+                "documents_written_total",
+                "Number of documents written to Elasticsearch",
+            ),
+            &run_labels,
+        )?;
+        let documents_deleted: IntCounterVec = IntCounterVec::new(
+            Opts::new(
+                "documents_deleted_total",
+                "Number of documents deleted from Elasticsearch",
+            ),
+            &run_labels,
+        )?;
+        let documents_failed: IntCounterVec = IntCounterVec::new(
+            Opts::new(
+                "documents_failed_total",
+                "Number of documents that failed to be indexed",
+            ),
+            &run_labels,
+        )?;
+        let bulk_request_duration: HistogramVec = HistogramVec::new(
+            HistogramOpts::new(
+                "es_bulk_request_duration_seconds",
+                "Latency of Elasticsearch bulk requests",
+            )
+            .buckets(BULK_DURATION_BUCKETS.to_vec()),
+            &["index"],
+        )?;
+        let bulk_errors: IntCounterVec = IntCounterVec::new(
+            Opts::new(
+                "es_bulk_errors_total",
+                "Number of failed Elasticsearch bulk requests and items",
+            ),
+            &["index", "kind"],
+        )?;
+        let es_pool_size: IntGauge = IntGauge::new(
+            "es_pool_size",
+            "Number of connections in the Elasticsearch connection pool",
+        )?;
+        let es_pool_idle: IntGauge = IntGauge::new(
+            "es_pool_idle_connections",
+            "Number of idle connections in the Elasticsearch connection pool",
+        )?;
+        let es_pool_in_use: IntGauge = IntGauge::new(
+            "es_pool_in_use_connections",
+            "Number of in-use connections in the Elasticsearch connection pool",
+        )?;
+        let watermark_age: IntGaugeVec = IntGaugeVec::new(
+            Opts::new(
+                "watermark_age_seconds",
+                "Seconds since the last indexed datetime of each index",
+            ),
+            &["index"],
+        )?;
+
+        let es_node_healthy: IntGaugeVec = IntGaugeVec::new(
+            Opts::new(
+                "es_node_healthy",
+                "Whether each Elasticsearch node is healthy (1) or quarantined (0)",
+            ),
+            &["host"],
+        )?;
+        let es_node_state_changes: IntCounterVec = IntCounterVec::new(
+            Opts::new(
+                "es_node_state_changes_total",
+                "Number of Elasticsearch node health state changes",
+            ),
+            &["host", "state"],
+        )?;
+
+        registry.register(Box::new(runs_started.clone()))?;
+        registry.register(Box::new(runs_succeeded.clone()))?;
+        registry.register(Box::new(runs_failed.clone()))?;
+        registry.register(Box::new(run_duration.clone()))?;
+        registry.register(Box::new(documents_read.clone()))?;
+        registry.register(Box::new(documents_written.clone()))?;
+        registry.register(Box::new(documents_deleted.clone()))?;
+        registry.register(Box::new(documents_failed.clone()))?;
+        registry.register(Box::new(bulk_request_duration.clone()))?;
+        registry.register(Box::new(bulk_errors.clone()))?;
+        registry.register(Box::new(es_pool_size.clone()))?;
+        registry.register(Box::new(es_pool_idle.clone()))?;
+        registry.register(Box::new(es_pool_in_use.clone()))?;
+        registry.register(Box::new(watermark_age.clone()))?;
+        registry.register(Box::new(es_node_healthy.clone()))?;
+        registry.register(Box::new(es_node_state_changes.clone()))?;
+
+        Ok(IndexingMetrics {
+            registry,
+            runs_started,
+            runs_succeeded,
+            runs_failed,
+            run_duration,
+            documents_read,
+            documents_written,
+            documents_deleted,
+            documents_failed,
+            bulk_request_duration,
+            bulk_errors,
+            es_pool_size,
+            es_pool_idle,
+            es_pool_in_use,
+            watermark_age,
+            es_node_healthy,
+            es_node_state_changes,
+            watermarks: std::sync::Mutex::new(HashMap::new()),
+        })
+    }
+
+    #[doc = "색인 작업 시작을 기록해주는 함수"]
+    /// # Arguments
+    /// * `index_name` - 인덱스(alias) 이름
+    /// * `function_name` - 색인 함수 이름
+    pub fn record_run_started(&self, index_name: &str, function_name: &str) {
+        self.runs_started
+            .with_label_values(&[index_name, function_name])
+            .inc();
+    }
+
+    #[doc = "색인 작업 결과와 처리 문서 수를 기록해주는 함수"]
+    /// # Arguments
+    /// * `index_name` - 인덱스(alias) 이름
+    /// * `function_name` - 색인 함수 이름
+    /// * `run_report` - 색인 작업 처리 결과
+    /// * `elapsed` - 소요시간
+    /// * `run_status` - 실행 이력 상태 (success, failed, canceled)
+    /// * `error_class` - 실패한 경우 실패 원인 분류
+    pub fn record_run_finished(
+        &self,
+        index_name: &str,
+        function_name: &str,
+        run_report: &RunReport,
+        elapsed: Duration,
+        run_status: &str,
+        error_class: Option<RunErrorClass>,
+    ) {
+        let labels: [&str; 2] = [index_name, function_name];
+
+        /* 취소된 작업은 성공, 실패 어느쪽에도 세지 않는다. */
+        match error_class {
+            Some(error_class) => {
+                self.runs_failed
+                    .with_label_values(&[index_name, function_name, error_class.as_str()])
+                    .inc();
+            }
+            None if run_status == RUN_STATUS_SUCCESS => {
+                self.runs_succeeded.with_label_values(&labels).inc();
+            }
+            None => (),
+        }
+
+        self.run_duration
+            .with_label_values(&[index_name, function_name, run_status])
+            .observe(elapsed.as_secs_f64());
+
+        self.documents_read
+            .with_label_values(&labels)
+            .inc_by(run_report.rows_read as u64);
+        self.documents_written
+            .with_label_values(&labels)
+            .inc_by(run_report.documents_written as u64);
+        self.documents_deleted
+            .with_label_values(&labels)
+            .inc_by(run_report.documents_deleted as u64);
+        self.documents_failed
+            .with_label_values(&labels)
+            .inc_by(run_report.documents_failed as u64);
+    }
+
+    #[doc = "bulk 요청 한번의 소요시간과 실패를 기록해주는 함수"]
+    /// # Arguments
+    /// * `index_name` - 색인 대상 인덱스 이름 (정적색인 세대 이름은 alias 로 묶어준다.)
+    /// * `elapsed` - 소요시간
+    /// * `request_failed` - 요청 자체가 실패했는지 여부
+    /// * `failed_item_cnt` - 응답 중 실패한 문서 수
+    pub fn record_bulk_request(
+        &self,
+        index_name: &str,
+        elapsed: Duration,
+        request_failed: bool,
+        failed_item_cnt: usize,
+    ) {
+        let index_label: &str = get_index_alias_label(index_name);
+
+        self.bulk_request_duration
+            .with_label_values(&[index_label])
+            .observe(elapsed.as_secs_f64());
+
+        if request_failed {
+            self.bulk_errors
+                .with_label_values(&[index_label, "request"])
+                .inc();
+        }
+
+        if failed_item_cnt > 0 {
+            self.bulk_errors
+                .with_label_values(&[index_label, "item"])
+                .inc_by(failed_item_cnt as u64);
+        }
+    }
+
+    #[doc = "Elasticsearch connection pool 의 전체 크기를 기록해주는 함수"]
+    pub fn set_es_pool_size(&self, pool_size: usize) {
+        self.es_pool_size.set(pool_size as i64);
+    }
+
+    #[doc = "Elasticsearch connection pool 의 유휴 connection 수를 기록해주는 함수"]
+    pub fn set_es_pool_idle(&self, idle_cnt: usize) {
+        self.es_pool_idle.set(idle_cnt as i64);
+    }
+
+    #[doc = "Elasticsearch connection pool 의 사용중인 connection 수를 기록해주는 함수"]
+    pub fn set_es_pool_in_use(&self, in_use_cnt: usize) {
+        self.es_pool_in_use.set(in_use_cnt as i64);
+    }
+
+    #[doc = "처음 등록된 Elasticsearch 노드를 정상으로 기록해주는 함수 -> 상태 변화가 아니므로 es_node_state_changes_total 은 늘리지 않는다."]
+    /// # Arguments
+    /// * `host` - 노드 주소
+    pub fn init_es_node(&self, host: &str) {
+        self.es_node_healthy.with_label_values(&[host]).set(1);
+    }
+
+    #[doc = "클러스터에서 빠진 Elasticsearch 노드의 상태 지표를 지워주는 함수"]
+    /// # Arguments
+    /// * `host` - 노드 주소
+    pub fn remove_es_node(&self, host: &str) {
+        if let Err(e) = self.es_node_healthy.remove_label_values(&[host]) {
+            warn!("[remove_es_node()] {:?}", e);
+        }
+    }
+
+    #[doc = "Elasticsearch 노드의 상태 변화를 기록해주는 함수"]
+    /// # Arguments
+    /// * `host` - 노드 주소
+    /// * `healthy` - 정상이면 true, 격리되었으면 false
+    pub fn set_es_node_healthy(&self, host: &str, healthy: bool) {
+        self.es_node_healthy
+            .with_label_values(&[host])
+            .set(i64::from(healthy));
+
+        let state: &str = if healthy { "healthy" } else { "quarantined" };
+        self.es_node_state_changes
+            .with_label_values(&[host, state])
+            .inc();
+    }
+
+    #[doc = "인덱스의 마지막 색인 시각(watermark)을 기록해주는 함수"]
+    /// # Arguments
+    /// * `index_name` - 인덱스(alias) 이름
+    /// * `watermark` - 마지막 색인 시각 (UTC)
+    pub fn record_watermark(&self, index_name: &str, watermark: NaiveDateTime) {
+        match self.watermarks.lock() {
+            Ok(mut watermarks) => {
+                watermarks.insert(index_name.to_string(), watermark);
+            }
+            Err(e) => error!("[Error][record_watermark()] {:?}", e),
+        }
+    }
+
+    #[doc = "등록된 지표들을 Prometheus text format 으로 만들어주는 함수"]
+    /// # Returns
+    /// * Result<String, anyhow::Error>
+    pub fn gather_text(&self) -> Result<String, anyhow::Error> {
+        /* watermark 경과 시간은 수집 시점 기준으로 계산해준다. */
+        let now: NaiveDateTime = Utc::now().naive_utc();
+
+        match self.watermarks.lock() {
+            Ok(watermarks) => {
+                for (index_name, watermark) in watermarks.iter() {
+                    self.watermark_age
+                        .with_label_values(&[index_name])
+                        .set((now - *watermark).num_seconds());
+                }
+            }
+            Err(e) => error!("[Error][gather_text()] {:?}", e),
+        }
+
+        let mut buffer: Vec<u8> = Vec::new();
+        TextEncoder::new().encode(&self.registry.gather(), &mut buffer)?;
+
+        Ok(String::from_utf8(buffer)?)
+    }
+}
+
+#[doc = "색인 프로그램의 Prometheus 지표를 가져와주는 함수"]
+pub fn get_indexing_metrics() -> &'static IndexingMetrics {
+    &INDEXING_METRICS
+}
+
+#[doc = "정적색인 세대 이름({alias}-{YYYYmmddHHMMSS})을 alias 로 바꿔주는 함수 -> 세대마다 label 이 늘어나지 않도록 한다."]
+/// # Arguments
+/// * `index_name` - 인덱스 이름
+///
+/// # Returns
+/// * &str
+fn get_index_alias_label(index_name: &str) -> &str {
+    match index_name.rsplit_once('-') {
+        Some((alias, suffix))
+            if suffix.len() == 14 && suffix.bytes().all(|b| b.is_ascii_digit()) =>
+        {
+            alias
+        }
+        _ => index_name,
+    }
+}
@@ -1,3 +1,4 @@
 pub mod io_utils;
 pub mod logger_utils;
+pub mod metrics_utils;
 pub mod time_utils;